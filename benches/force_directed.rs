@@ -0,0 +1,64 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Bencher, BenchmarkId, Criterion};
+use egui::Pos2;
+use egui_graphs::{ForceEuclidean, Graph, LayoutForceDirected};
+use petgraph::stable_graph::NodeIndex;
+use rand::Rng;
+
+/// Graph with nodes on a square grid and as many random edges as nodes.
+///
+/// Nodes are added with a location the layout starts from, so every step moves them.
+fn graph(size: usize) -> Graph {
+    let mut rng = rand::thread_rng();
+    let mut g = Graph::default();
+    let columns = (size as f32).sqrt() as usize;
+    for i in 0..size {
+        let loc = Pos2::new((i % columns) as f32, (i / columns) as f32) * 10.;
+        g.add_node_with_location((), loc);
+    }
+    for _ in 0..size {
+        let (source, target) = (rng.gen_range(0..size), rng.gen_range(0..size));
+        g.add_edge(NodeIndex::new(source), NodeIndex::new(target), ());
+    }
+
+    g
+}
+
+fn bench_step(b: &mut Bencher, g: &Graph) {
+    b.iter_batched(
+        || (g.clone(), LayoutForceDirected::<ForceEuclidean>::default()),
+        |(mut g, mut layout)| layout.step(&mut g),
+        BatchSize::LargeInput,
+    );
+}
+
+/// One simulation step of the force-directed layout. With the `rayon` feature the step is
+/// measured on a single thread and on the default thread pool side by side.
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("force_directed_step");
+    group.sample_size(10);
+
+    for size in [10_000, 100_000] {
+        let g = graph(size);
+
+        group.bench_function(BenchmarkId::new("serial", size), |b| {
+            #[cfg(feature = "rayon")]
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap()
+                .install(|| bench_step(b, &g));
+            #[cfg(not(feature = "rayon"))]
+            bench_step(b, &g);
+        });
+
+        #[cfg(feature = "rayon")]
+        group.bench_function(BenchmarkId::new("parallel", size), |b| {
+            bench_step(b, &g);
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
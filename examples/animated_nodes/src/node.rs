@@ -99,7 +99,7 @@ impl<N: Clone + IsClockwise, E: Clone, Ty: EdgeType, Ix: IndexType> DisplayNode<
         let center = ctx.meta.canvas_to_screen_pos(self.loc);
         let size = ctx.meta.canvas_to_screen_size(self.size);
         let rect_default = Rect::from_center_size(center, Vec2::new(size, size));
-        let color = ctx.ctx.style().visuals.weak_text_color();
+        let color = ctx.visuals.weak_text_color();
 
         let diff = match self.dragged {
             true => self.get_rotation_increment(),
@@ -125,7 +125,7 @@ impl<N: Clone + IsClockwise, E: Clone, Ty: EdgeType, Ix: IndexType> DisplayNode<
         let shape_rect = Shape::convex_polygon(points, Color32::default(), Stroke::new(1., color));
 
         // create label
-        let color = ctx.ctx.style().visuals.text_color();
+        let color = ctx.visuals.text_color();
         let galley = ctx.ctx.fonts(|f| {
             f.layout_no_wrap(
                 self.label.clone(),
@@ -37,7 +37,7 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType> DisplayNode<N, E, Ty, Ix>
     fn shapes(&mut self, ctx: &egui_graphs::DrawContext) -> Vec<egui::Shape> {
         // find node center location on the screen coordinates
         let center = ctx.meta.canvas_to_screen_pos(self.loc);
-        let color = ctx.ctx.style().visuals.text_color();
+        let color = ctx.visuals.text_color();
 
         // create label
         let galley = ctx.ctx.fonts(|f| {
@@ -2,13 +2,14 @@ use eframe::{run_native, App, CreationContext, NativeOptions};
 use egui::Context;
 use egui_graphs::{
     random_graph, DefaultEdgeShape, DefaultNodeShape, Graph, GraphView, LayoutHierarchical,
-    LayoutRandom, LayoutStateHierarchical, LayoutStateRandom,
+    LayoutLayered, LayoutRandom, LayoutStateHierarchical, LayoutStateLayered, LayoutStateRandom,
 };
 use petgraph::{stable_graph::DefaultIx, Directed};
 
 #[derive(Clone, PartialEq)]
 enum Layout {
     Hierarchical,
+    Layered,
     Random,
 }
 
@@ -50,6 +51,18 @@ impl LayoutsApp {
                     LayoutHierarchical,
                 >::clear_cache(ui);
             }
+            Layout::Layered => {
+                GraphView::<
+                    (),
+                    (),
+                    Directed,
+                    DefaultIx,
+                    DefaultNodeShape,
+                    DefaultEdgeShape,
+                    LayoutStateLayered,
+                    LayoutLayered,
+                >::clear_cache(ui);
+            }
             Layout::Random => {
                 GraphView::<
                     (),
@@ -84,6 +97,12 @@ impl App for LayoutsApp {
                         {
                             self.clear_cache(ui);
                         };
+                        if ui
+                            .radio_value(&mut self.settings.layout, Layout::Layered, "Layered")
+                            .changed()
+                        {
+                            self.clear_cache(ui);
+                        };
                         if ui
                             .radio_value(&mut self.settings.layout, Layout::Random, "Random")
                             .changed()
@@ -127,6 +146,18 @@ impl App for LayoutsApp {
                         LayoutHierarchical,
                     >::new(&mut self.g));
                 }
+                Layout::Layered => {
+                    ui.add(&mut GraphView::<
+                        _,
+                        _,
+                        _,
+                        _,
+                        _,
+                        _,
+                        LayoutStateLayered,
+                        LayoutLayered,
+                    >::new(&mut self.g));
+                }
                 Layout::Random => {
                     ui.add(&mut GraphView::<
                         _,
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use egui::{Pos2, Vec2};
+
+/// Number of direction buckets edges are grouped by.
+const ANGLE_BUCKETS: f32 = 16.;
+/// Size in canvas units of the distance buckets between edge lines and the origin.
+const OFFSET_BUCKET: f32 = 50.;
+
+/// Groups edges lying close to the same line and pulls their middle parts to the shared spine of the group.
+///
+/// Edges are given by start and end positions in canvas coordinates. Returns control points of cubic
+/// bezier curves for bundled edges and `None` for edges which have no similar edges. `strength` in range `[0, 1]`
+/// controls how close the control points move to the spine: `0` keeps edges straight, `1` makes them
+/// meet on the spine.
+pub(crate) fn bundle(edges: &[(Pos2, Pos2)], strength: f32) -> Vec<Option<[Pos2; 2]>> {
+    let mut res = vec![None; edges.len()];
+    let strength = strength.clamp(0., 1.);
+    if strength == 0. {
+        return res;
+    }
+
+    // edges are grouped in the space of line direction and offset from the origin
+    let mut groups: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (i, (start, end)) in edges.iter().enumerate() {
+        if start.distance(*end) <= f32::EPSILON {
+            continue;
+        }
+
+        let angle_bucket =
+            (angle(*start, *end) / PI * ANGLE_BUCKETS).round() as i32 % ANGLE_BUCKETS as i32;
+
+        // offset is measured along the normal of the bucket direction so that edges with directions
+        // close to the bucket bounds do not flip its sign
+        let normal = Vec2::angled(angle_bucket as f32 * PI / ANGLE_BUCKETS).rot90();
+        let offset = normal.dot((start.to_vec2() + end.to_vec2()) / 2.);
+        let offset_bucket = (offset / OFFSET_BUCKET).floor() as i32;
+        groups
+            .entry((angle_bucket, offset_bucket))
+            .or_default()
+            .push(i);
+    }
+
+    for ((angle_bucket, _), members) in groups {
+        if members.len() < 2 {
+            continue;
+        }
+
+        let angle = angle_bucket as f32 * PI / ANGLE_BUCKETS;
+        let spine_dir = Vec2::angled(angle);
+        let spine_center = members
+            .iter()
+            .map(|i| (edges[*i].0.to_vec2() + edges[*i].1.to_vec2()) / 2.)
+            .fold(Vec2::ZERO, |acc, mid| acc + mid)
+            / members.len() as f32;
+
+        let pull = |p: Pos2| {
+            let on_spine = spine_center + spine_dir * (p.to_vec2() - spine_center).dot(spine_dir);
+            p + (on_spine - p.to_vec2()) * strength
+        };
+
+        for i in members {
+            let (start, end) = edges[i];
+            let dist = end - start;
+            res[i] = Some([pull(start + dist / 3.), pull(start + dist * 2. / 3.)]);
+        }
+    }
+
+    res
+}
+
+/// Returns direction angle of the line going through the points in range `[0, PI)`.
+fn angle(start: Pos2, end: Pos2) -> f32 {
+    let mut angle = (end - start).angle();
+    if angle < 0. {
+        angle += PI;
+    }
+    if angle >= PI {
+        angle -= PI;
+    }
+
+    angle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_parallel_edges() {
+        let edges = [
+            (Pos2::new(0., 10.), Pos2::new(300., 10.)),
+            (Pos2::new(0., 20.), Pos2::new(300., 21.)),
+            // opposite direction still bundles
+            (Pos2::new(300., 30.), Pos2::new(0., 29.)),
+            // perpendicular edge is left alone
+            (Pos2::new(500., 0.), Pos2::new(500., 300.)),
+        ];
+
+        let res = bundle(&edges, 1.);
+
+        assert!(res[3].is_none());
+        let [a, b, c] = [res[0].unwrap(), res[1].unwrap(), res[2].unwrap()];
+        assert!((a[0].y - 20.).abs() < 1e-3);
+        assert!((b[0].y - 20.).abs() < 1e-3);
+        assert!((c[1].y - 20.).abs() < 1e-3);
+        assert!((a[0].x - 100.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_bundle_strength() {
+        let edges = [
+            (Pos2::new(0., 10.), Pos2::new(300., 10.)),
+            (Pos2::new(0., 20.), Pos2::new(300., 20.)),
+        ];
+
+        assert_eq!(bundle(&edges, 0.), vec![None, None]);
+
+        let half = bundle(&edges, 0.5);
+        assert!((half[0].unwrap()[0].y - 12.5).abs() < 1e-3);
+        assert!((half[1].unwrap()[1].y - 17.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_bundle_degenerate() {
+        let edges = [
+            (Pos2::new(1., 1.), Pos2::new(1., 1.)),
+            (Pos2::new(1., 1.), Pos2::new(1., 1.)),
+        ];
+
+        assert_eq!(bundle(&edges, 1.), vec![None, None]);
+    }
+}
@@ -77,4 +77,18 @@ where
         end: &Node<N, E, Ty, Ix, D>,
         pos: Pos2,
     ) -> bool;
+
+    /// Checks if the provided `pos` is inside the shape or within `padding` canvas units from it.
+    /// Used to find edges under the pointer with hit padding, see `SettingsInteraction::with_edge_hit_padding`.
+    ///
+    /// The default implementation ignores the padding and falls back to [`DisplayEdge::is_inside`].
+    fn is_inside_with_padding(
+        &self,
+        start: &Node<N, E, Ty, Ix, D>,
+        end: &Node<N, E, Ty, Ix, D>,
+        pos: Pos2,
+        _padding: f32,
+    ) -> bool {
+        self.is_inside(start, end, pos)
+    }
 }
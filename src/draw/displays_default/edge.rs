@@ -6,21 +6,39 @@ use egui::{
 };
 use petgraph::{stable_graph::IndexType, EdgeType};
 
-use crate::{draw::DrawContext, elements::EdgeProps, node_size, DisplayEdge, DisplayNode, Node};
+use crate::{
+    draw::DrawContext, elements::EdgeProps, node_size, DisplayEdge, DisplayNode, EdgeRouting, Node,
+    ReciprocalEdges,
+};
 
-use super::edge_shape_builder::{EdgeShapeBuilder, TipProps};
+use super::edge_shape_builder::{orthogonal_points, routing_axis, EdgeShapeBuilder, TipProps};
 
 #[derive(Clone, Debug)]
 pub struct DefaultEdgeShape {
     pub order: usize,
     pub selected: bool,
+    pub hovered: bool,
 
     pub width: f32,
     pub tip_size: f32,
     pub tip_angle: f32,
     pub curve_size: f32,
     pub loop_size: f32,
+    /// Offset of the edge middle from the straight line set with `Edge::set_curvature`
+    pub curvature: f32,
     pub label_text: String,
+    /// Routing of the edge, taken from the style when the edge is drawn
+    pub routing: EdgeRouting,
+    /// Distance between the ends of the edge and node boundaries, taken from the style when the edge is drawn
+    pub end_gap: f32,
+
+    /// Drawing together with the opposite edge, set by the drawer
+    pub reciprocal: ReciprocalEdges,
+    /// Whether there is an edge in the opposite direction, set by the drawer
+    pub mutual: bool,
+
+    /// Control points of the curve computed by edge bundling
+    pub bundle: Option<[Pos2; 2]>,
 }
 
 impl<E: Clone> From<EdgeProps<E>> for DefaultEdgeShape {
@@ -28,13 +46,20 @@ impl<E: Clone> From<EdgeProps<E>> for DefaultEdgeShape {
         Self {
             order: edge.order,
             selected: edge.selected,
+            hovered: edge.hovered(),
+            bundle: edge.bundle(),
+            reciprocal: edge.reciprocal(),
+            mutual: edge.mutual(),
             label_text: edge.label,
+            curvature: edge.curvature,
 
             width: 2.,
             tip_size: 15.,
             tip_angle: std::f32::consts::TAU / 30.,
             curve_size: 20.,
             loop_size: 3.,
+            routing: EdgeRouting::default(),
+            end_gap: 0.,
         }
     }
 }
@@ -48,18 +73,17 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, I
         end: &Node<N, E, Ty, Ix, D>,
         pos: egui::Pos2,
     ) -> bool {
-        if start.id() == end.id() {
-            return self.is_inside_loop(start, pos);
-        }
-
-        let pos_start = start.location();
-        let pos_end = end.location();
-
-        if self.order == 0 {
-            return self.is_inside_line(pos_start, pos_end, pos);
-        }
+        self.distance(start, end, pos) <= self.width
+    }
 
-        self.is_inside_curve(start, end, pos)
+    fn is_inside_with_padding(
+        &self,
+        start: &Node<N, E, Ty, Ix, D>,
+        end: &Node<N, E, Ty, Ix, D>,
+        pos: Pos2,
+        padding: f32,
+    ) -> bool {
+        self.distance(start, end, pos) <= self.width + padding
     }
 
     #[allow(clippy::too_many_lines)] // TODO: refactor
@@ -70,141 +94,135 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, I
         ctx: &DrawContext,
     ) -> Vec<egui::Shape> {
         let mut res = vec![];
+        self.end_gap = ctx.style.edge_end_gap;
 
         let label_visible = ctx.style.labels_always || self.selected;
 
-        let style = if self.selected {
-            ctx.ctx.style().visuals.widgets.active
+        let visuals = ctx.visuals;
+        let color = if self.selected {
+            ctx.style
+                .edge_selected_color
+                .unwrap_or(visuals.widgets.active.fg_stroke.color)
+        } else if self.hovered {
+            ctx.style
+                .edge_hover_color
+                .unwrap_or(visuals.widgets.hovered.fg_stroke.color)
+        } else if self.mutual {
+            ctx.style
+                .reciprocal_edge_color
+                .unwrap_or(visuals.widgets.inactive.fg_stroke.color)
         } else {
-            ctx.ctx.style().visuals.widgets.inactive
+            visuals.widgets.inactive.fg_stroke.color
         };
-        let color = style.fg_stroke.color;
+        // edges fade together with their nodes
+        let opacity = start.opacity().min(end.opacity());
+        let color = color.gamma_multiply(opacity);
         let stroke = Stroke::new(self.width, color);
+        // interacted edges keep their state color
+        let gradient = (ctx.style.edge_gradient && !self.selected && !self.hovered).then(|| {
+            (
+                start.color().map_or(color, |c| c.gamma_multiply(opacity)),
+                end.color().map_or(color, |c| c.gamma_multiply(opacity)),
+            )
+        });
 
         if start.id() == end.id() {
             // draw loop
             let size = node_size(start, Vec2::new(-1., 0.));
-            let mut line_looped_shapes = EdgeShapeBuilder::new(stroke)
+            let line_looped_shapes = EdgeShapeBuilder::new(stroke)
                 .looped(start.location(), size, self.loop_size, self.order)
                 .with_scaler(ctx.meta)
+                .with_cap(ctx.style.edge_cap)
                 .build();
-            let line_looped_shape = line_looped_shapes.clone().pop().unwrap();
-            res.push(line_looped_shape);
-
-            let Shape::CubicBezier(line_looped) = line_looped_shapes.pop().unwrap() else {
+            let Some(Shape::CubicBezier(line_looped)) = line_looped_shapes.first().cloned() else {
                 panic!("invalid shape type")
             };
+            res.extend(line_looped_shapes);
 
-            // TODO: export to func
             if label_visible {
-                let galley = ctx.ctx.fonts(|f| {
-                    f.layout_no_wrap(
-                        self.label_text.clone(),
-                        FontId::new(ctx.meta.canvas_to_screen_size(size), FontFamily::Monospace),
-                        color,
-                    )
-                });
-
                 let flattened_curve = line_looped.flatten(None);
                 let median = *flattened_curve.get(flattened_curve.len() / 2).unwrap();
-
-                let label_width = galley.rect.width();
-                let label_height = galley.rect.height();
-                let pos = Pos2::new(median.x - label_width / 2., median.y - label_height);
-
-                let label_shape = TextShape::new(pos, galley, color);
-                res.push(label_shape.into());
+                res.push(self.label_shape(ctx, size, median, color));
             }
             return res;
         }
 
         let dir = (end.location() - start.location()).normalized();
-        let start_connector_point = start.display().closest_boundary_point(dir);
-        let end_connector_point = end.display().closest_boundary_point(-dir);
+        let label_size = f32::midpoint(node_size(start, dir), node_size(end, dir));
 
-        if self.order == 0 {
-            // draw straight edge
+        if let (true, Some(bundle)) = (self.is_straight(), self.bundle) {
+            let (start_connector_point, end_connector_point) =
+                bundle_bounds(start, end, bundle, self.end_gap);
+            let builder = EdgeShapeBuilder::new(stroke)
+                .bundled((start_connector_point, end_connector_point), bundle)
+                .with_scaler(ctx.meta)
+                .with_cap(ctx.style.edge_cap)
+                .with_gradient(gradient);
+            res.extend(self.build_with_tip(builder, ctx));
 
-            let mut builder = EdgeShapeBuilder::new(stroke)
-                .straight((start_connector_point, end_connector_point))
-                .with_scaler(ctx.meta);
+            if label_visible {
+                // label is placed between control points where bundled edges are most spread
+                let center = ctx
+                    .meta
+                    .canvas_to_screen_pos(bundle[0] + (bundle[1] - bundle[0]) / 2.);
+                res.push(self.label_shape(ctx, label_size, center, color));
+            }
 
-            let tip_props = TipProps {
-                size: self.tip_size,
-                angle: self.tip_angle,
-            };
-            if ctx.is_directed {
-                builder = builder.with_tip(&tip_props);
+            return res;
+        }
+
+        let start_connector_point = connector_point(start, dir, self.end_gap);
+        let end_connector_point = connector_point(end, -dir, self.end_gap);
+
+        if self.is_straight() {
+            // draw straight or routed edge
+            self.routing = ctx.style.edge_routing;
+            let (start_connector_point, end_connector_point, builder) = match self.routing {
+                EdgeRouting::Straight => (
+                    start_connector_point,
+                    end_connector_point,
+                    EdgeShapeBuilder::new(stroke)
+                        .straight((start_connector_point, end_connector_point)),
+                ),
+                routing => {
+                    let (axis, bounds) = routed_bounds(start, end, self.end_gap);
+                    let builder = EdgeShapeBuilder::new(stroke);
+                    let builder = if routing == EdgeRouting::Bezier {
+                        builder.bezier(bounds, axis)
+                    } else {
+                        builder.orthogonal(bounds, axis)
+                    };
+                    (bounds.0, bounds.1, builder)
+                }
             };
-            let straight_shapes = builder.build();
-            res.extend(straight_shapes);
+            let builder = builder
+                .with_scaler(ctx.meta)
+                .with_cap(ctx.style.edge_cap)
+                .with_gradient(gradient);
+            res.extend(self.build_with_tip(builder, ctx));
 
-            // TODO: export to func
             if label_visible {
-                let size = (node_size(start, dir) + node_size(end, dir)) / 2.;
-                let galley = ctx.ctx.fonts(|f| {
-                    f.layout_no_wrap(
-                        self.label_text.clone(),
-                        FontId::new(ctx.meta.canvas_to_screen_size(size), FontFamily::Monospace),
-                        color,
-                    )
-                });
-
                 let dist = end_connector_point - start_connector_point;
                 let center = ctx
                     .meta
                     .canvas_to_screen_pos(start_connector_point + dist / 2.);
-                let label_width = galley.rect.width();
-                let label_height = galley.rect.height();
-                let pos = Pos2::new(center.x - label_width / 2., center.y - label_height);
-
-                let label_shape = TextShape::new(pos, galley, color);
-                res.push(label_shape.into());
+                res.push(self.label_shape(ctx, label_size, center, color));
             }
 
             return res;
         }
 
-        let mut builder = EdgeShapeBuilder::new(stroke)
-            .curved(
-                (start_connector_point, end_connector_point),
-                self.curve_size,
-                self.order,
-            )
-            .with_scaler(ctx.meta);
-
-        let tip_props = TipProps {
-            size: self.tip_size,
-            angle: self.tip_angle,
-        };
-        if ctx.is_directed {
-            builder = builder.with_tip(&tip_props);
-        };
-        let curved_shapes = builder.build();
-        let Some(Shape::CubicBezier(line_curved)) = curved_shapes.first() else {
-            panic!("invalid shape type")
-        };
-        res.extend(curved_shapes.clone());
+        let builder = self
+            .curve_builder(stroke, (start_connector_point, end_connector_point))
+            .with_scaler(ctx.meta)
+            .with_cap(ctx.style.edge_cap)
+            .with_gradient(gradient);
+        let curved_shapes = self.build_with_tip(builder, ctx);
+        let median = curve_median(&curved_shapes);
+        res.extend(curved_shapes);
 
         if label_visible {
-            let size = (node_size(start, dir) + node_size(end, dir)) / 2.;
-            let galley = ctx.ctx.fonts(|f| {
-                f.layout_no_wrap(
-                    self.label_text.clone(),
-                    FontId::new(ctx.meta.canvas_to_screen_size(size), FontFamily::Monospace),
-                    color,
-                )
-            });
-
-            let flattened_curve = line_curved.flatten(None);
-            let median = *flattened_curve.get(flattened_curve.len() / 2).unwrap();
-
-            let label_width = galley.rect.width();
-            let label_height = galley.rect.height();
-            let pos = Pos2::new(median.x - label_width / 2., median.y - label_height);
-
-            let label_shape = TextShape::new(pos, galley, color);
-            res.push(label_shape.into());
+            res.push(self.label_shape(ctx, label_size, median, color));
         }
 
         res
@@ -213,12 +231,96 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, I
     fn update(&mut self, state: &EdgeProps<E>) {
         self.order = state.order;
         self.selected = state.selected;
+        self.hovered = state.hovered();
         self.label_text = state.label.to_string();
+        self.bundle = state.bundle();
+        self.curvature = state.curvature;
+        self.reciprocal = state.reciprocal();
+        self.mutual = state.mutual();
     }
 }
 
 impl DefaultEdgeShape {
-    fn is_inside_loop<
+    /// Builds shapes of the edge adding the tip in directed graphs.
+    fn build_with_tip(&self, builder: EdgeShapeBuilder, ctx: &DrawContext) -> Vec<Shape> {
+        let tip_props = TipProps {
+            size: self.tip_size,
+            angle: self.tip_angle,
+            placement: ctx.style.arrow_placement,
+            at_start: self.reciprocal == ReciprocalEdges::Combined,
+        };
+        if ctx.is_directed {
+            builder.with_tip(&tip_props).build()
+        } else {
+            builder.build()
+        }
+    }
+
+    /// Label of the edge centered horizontally above the point in screen coordinates.
+    /// Font size is in canvas units and scales with zoom.
+    fn label_shape(&self, ctx: &DrawContext, size: f32, pos: Pos2, color: Color32) -> Shape {
+        let galley = ctx.ctx.fonts(|f| {
+            f.layout_no_wrap(
+                self.label_text.clone(),
+                FontId::new(ctx.meta.canvas_to_screen_size(size), FontFamily::Monospace),
+                color,
+            )
+        });
+
+        let pos = Pos2::new(
+            pos.x - galley.rect.width() / 2.,
+            pos.y - galley.rect.height(),
+        );
+        TextShape::new(pos, galley, color).into()
+    }
+
+    /// Whether the edge is neither a parallel one nor bent with a curvature.
+    fn is_straight(&self) -> bool {
+        self.order == 0 && self.curvature == 0. && self.reciprocal != ReciprocalEdges::Curved
+    }
+
+    /// Parallel edges are spread by their order, the curvature bends them further. Opposite edges
+    /// bow to their own left side, which separates them.
+    fn curve_builder(&self, stroke: Stroke, bounds: (Pos2, Pos2)) -> EdgeShapeBuilder<'_> {
+        let builder = EdgeShapeBuilder::new(stroke);
+        if self.curvature == 0. && self.order > 0 {
+            return builder.curved(bounds, self.curve_size, self.order);
+        }
+
+        let offset = if self.order == 0 && self.reciprocal == ReciprocalEdges::Curved {
+            self.curve_size / 2.
+        } else {
+            self.curve_size * self.order as f32
+        };
+        builder.bowed(bounds, offset + self.curvature)
+    }
+
+    /// Distance in canvas units from the position to the line of the edge.
+    fn distance<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, Ix>>(
+        &self,
+        start: &Node<N, E, Ty, Ix, D>,
+        end: &Node<N, E, Ty, Ix, D>,
+        pos: Pos2,
+    ) -> f32 {
+        if start.id() == end.id() {
+            return self.distance_loop(start, pos);
+        }
+
+        if let (true, Some(bundle)) = (self.is_straight(), self.bundle) {
+            return self.distance_bundled(start, end, bundle, pos);
+        }
+
+        if self.is_straight() {
+            if self.routing != EdgeRouting::Straight {
+                return self.distance_routed(start, end, pos);
+            }
+            return distance_segment_to_point(start.location(), end.location(), pos);
+        }
+
+        self.distance_curve(start, end, pos)
+    }
+
+    fn distance_loop<
         E: Clone,
         N: Clone,
         Ix: IndexType,
@@ -228,7 +330,7 @@ impl DefaultEdgeShape {
         &self,
         node: &Node<N, E, Ty, Ix, D>,
         pos: Pos2,
-    ) -> bool {
+    ) -> f32 {
         let node_size = node_size(node, Vec2::new(-1., 0.));
 
         let shape = EdgeShapeBuilder::new(Stroke::new(self.width, Color32::default()))
@@ -236,17 +338,38 @@ impl DefaultEdgeShape {
             .build();
 
         match shape.first() {
-            Some(Shape::CubicBezier(cubic)) => is_point_on_curve(pos, cubic),
+            Some(Shape::CubicBezier(cubic)) => distance_curve_to_point(cubic, pos),
             _ => panic!("invalid shape type"),
         }
     }
 
-    fn is_inside_line(&self, pos_start: Pos2, pos_end: Pos2, pos: Pos2) -> bool {
-        let distance = distance_segment_to_point(pos_start, pos_end, pos);
-        distance <= self.width
+    fn distance_bundled<
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        D: DisplayNode<N, E, Ty, Ix>,
+    >(
+        &self,
+        node_start: &Node<N, E, Ty, Ix, D>,
+        node_end: &Node<N, E, Ty, Ix, D>,
+        bundle: [Pos2; 2],
+        pos: Pos2,
+    ) -> f32 {
+        let shapes = EdgeShapeBuilder::new(Stroke::new(self.width, Color32::default()))
+            .bundled(
+                bundle_bounds(node_start, node_end, bundle, self.end_gap),
+                bundle,
+            )
+            .build();
+
+        match shapes.first() {
+            Some(Shape::CubicBezier(curve)) => distance_curve_to_point(curve, pos),
+            _ => panic!("invalid shape type"),
+        }
     }
 
-    fn is_inside_curve<
+    fn distance_routed<
         N: Clone,
         E: Clone,
         Ty: EdgeType,
@@ -257,23 +380,115 @@ impl DefaultEdgeShape {
         node_start: &Node<N, E, Ty, Ix, D>,
         node_end: &Node<N, E, Ty, Ix, D>,
         pos: Pos2,
-    ) -> bool {
+    ) -> f32 {
+        let (axis, bounds) = routed_bounds(node_start, node_end, self.end_gap);
+        if self.routing == EdgeRouting::Orthogonal {
+            return distance_polyline_to_point(&orthogonal_points(bounds, axis), pos);
+        }
+
+        let shapes = EdgeShapeBuilder::new(Stroke::new(self.width, Color32::default()))
+            .bezier(bounds, axis)
+            .build();
+        match shapes.first() {
+            Some(Shape::CubicBezier(curve)) => distance_curve_to_point(curve, pos),
+            _ => panic!("invalid shape type"),
+        }
+    }
+
+    fn distance_curve<
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        D: DisplayNode<N, E, Ty, Ix>,
+    >(
+        &self,
+        node_start: &Node<N, E, Ty, Ix, D>,
+        node_end: &Node<N, E, Ty, Ix, D>,
+        pos: Pos2,
+    ) -> f32 {
         let dir = (node_end.location() - node_start.location()).normalized();
-        let start = node_start.display().closest_boundary_point(dir);
-        let end = node_end.display().closest_boundary_point(-dir);
+        let start = connector_point(node_start, dir, self.end_gap);
+        let end = connector_point(node_end, -dir, self.end_gap);
 
-        let curved_shapes = EdgeShapeBuilder::new(Stroke::new(self.width, Color32::default()))
-            .curved((start, end), self.curve_size, self.order)
+        let curved_shapes = self
+            .curve_builder(Stroke::new(self.width, Color32::default()), (start, end))
             .build();
         let curved_shape = match curved_shapes.first() {
             Some(Shape::CubicBezier(curve)) => curve.clone(),
             _ => panic!("invalid shape type"),
         };
 
-        is_point_on_curve(pos, &curved_shape)
+        distance_curve_to_point(&curved_shape, pos)
     }
 }
 
+/// Returns points on node boundaries where the bundled edge leaves the start node towards the first control point
+/// and enters the end node from the second one.
+fn bundle_bounds<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, Ix>>(
+    start: &Node<N, E, Ty, Ix, D>,
+    end: &Node<N, E, Ty, Ix, D>,
+    bundle: [Pos2; 2],
+    gap: f32,
+) -> (Pos2, Pos2) {
+    let dir_start = (bundle[0] - start.location()).normalized();
+    let dir_end = (bundle[1] - end.location()).normalized();
+
+    (
+        connector_point(start, dir_start, gap),
+        connector_point(end, dir_end, gap),
+    )
+}
+
+/// Returns routing axis between the nodes and points on node boundaries where the routed edge leaves
+/// the start node and enters the end node along the axis.
+fn routed_bounds<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, Ix>>(
+    start: &Node<N, E, Ty, Ix, D>,
+    end: &Node<N, E, Ty, Ix, D>,
+    gap: f32,
+) -> (Vec2, (Pos2, Pos2)) {
+    let axis = routing_axis(start.location(), end.location());
+
+    (
+        axis,
+        (
+            connector_point(start, axis, gap),
+            connector_point(end, -axis, gap),
+        ),
+    )
+}
+
+/// Returns the point in the middle of the curve, drawn either as a single bezier or as gradient segments.
+fn curve_median(shapes: &[Shape]) -> Pos2 {
+    let points = match shapes.first() {
+        Some(Shape::CubicBezier(curve)) => curve.flatten(None),
+        _ => shapes
+            .iter()
+            .filter_map(|s| match s {
+                Shape::LineSegment { points, .. } => Some(points[0]),
+                _ => None,
+            })
+            .collect(),
+    };
+
+    points.get(points.len() / 2).copied().unwrap_or_default()
+}
+
+/// Returns the point where the edge leaving the node in `dir` starts, `gap` canvas units away from its boundary.
+fn connector_point<
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    D: DisplayNode<N, E, Ty, Ix>,
+>(
+    node: &Node<N, E, Ty, Ix, D>,
+    dir: Vec2,
+    gap: f32,
+) -> Pos2 {
+    node.display().closest_boundary_point(dir) + dir.normalized() * gap
+}
+
 /// Returns the distance from line segment `a``b` to point `c`.
 /// Adapted from <https://stackoverflow.com/questions/1073336/circle-line-segment-collision-detection-algorithm>
 fn distance_segment_to_point(a: Pos2, b: Pos2, point: Pos2) -> f32 {
@@ -310,18 +525,78 @@ fn proj(a: Vec2, b: Vec2) -> Vec2 {
     Vec2::new(k * b.x, k * b.y)
 }
 
-fn is_point_on_curve(point: Pos2, curve: &CubicBezierShape) -> bool {
-    for p in curve.flatten(None) {
-        if p.distance(point) < curve.stroke.width {
-            return true;
-        }
-    }
-    false
+/// Distance from the point to the closest segment of the polyline, infinite if there are no segments.
+fn distance_polyline_to_point(points: &[Pos2], point: Pos2) -> f32 {
+    points
+        .windows(2)
+        .filter(|w| w[0] != w[1])
+        .map(|w| distance_segment_to_point(w[0], w[1], point))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Distance from the point to the flattened curve.
+fn distance_curve_to_point(curve: &CubicBezierShape, point: Pos2) -> f32 {
+    distance_polyline_to_point(&curve.flatten(None), point)
 }
 
 #[cfg(test)]
 mod tests {
+    use egui::{epaint::ColorMode, Context};
+    use petgraph::graph::NodeIndex;
+
     use super::*;
+    use crate::export::shapes;
+    use crate::test_util::graph;
+    use crate::{Metadata, SettingsStyle};
+
+    #[test]
+    fn test_edge_end_gap() {
+        let mut g = graph();
+        g.node_mut(NodeIndex::new(1))
+            .unwrap()
+            .set_location(Pos2::new(50., 0.));
+
+        let mut meta = Metadata::default();
+        meta.zoom = 2.;
+        let line_start = |style: &SettingsStyle| {
+            shapes(&Context::default(), &g, style, &meta)
+                .iter()
+                .find_map(|s| match s {
+                    Shape::Path(p) if !p.closed => p.points.first().copied(),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        // node radius 5 and gap 3 in canvas units at zoom 2
+        assert_eq!(line_start(&SettingsStyle::default()).x, 10.);
+        assert_eq!(
+            line_start(&SettingsStyle::default().with_edge_end_gap(3.)).x,
+            16.
+        );
+    }
+
+    #[test]
+    fn test_reciprocal_edge_color() {
+        let mut g = graph();
+        let first = NodeIndex::new(0);
+        let second = NodeIndex::new(1);
+        let third = g.add_node(());
+        g.add_edge(second, first, ());
+        g.add_edge(second, third, ());
+        g.node_mut(third)
+            .unwrap()
+            .set_location(Pos2::new(-50., 20.));
+
+        let color = Color32::from_rgb(200, 30, 30);
+        let style = SettingsStyle::default().with_reciprocal_edge_color(Some(color));
+        let colored = shapes(&Context::default(), &g, &style, &Metadata::default())
+            .iter()
+            .filter(|s| matches!(s, Shape::Path(p) if !p.closed && p.stroke.color == ColorMode::Solid(color)))
+            .count();
+
+        assert_eq!(colored, 2);
+    }
 
     #[test]
     fn test_distance_segment_to_point() {
@@ -1,8 +1,11 @@
 use std::f32::consts::PI;
 
-use egui::{epaint::CubicBezierShape, Color32, Pos2, Shape, Stroke, Vec2};
+use egui::{
+    epaint::{CubicBezierShape, PathShape},
+    Color32, Pos2, Shape, Stroke, Vec2,
+};
 
-use crate::Metadata;
+use crate::{ArrowPlacement, EdgeCap, Metadata};
 
 enum EdgeShapeProps {
     Straight {
@@ -13,6 +16,22 @@ enum EdgeShapeProps {
         curve_size: f32,
         order: usize,
     },
+    Bowed {
+        bounds: (Pos2, Pos2),
+        offset: f32,
+    },
+    Bundled {
+        bounds: (Pos2, Pos2),
+        control_points: [Pos2; 2],
+    },
+    Bezier {
+        bounds: (Pos2, Pos2),
+        axis: Vec2,
+    },
+    Orthogonal {
+        bounds: (Pos2, Pos2),
+        axis: Vec2,
+    },
     Looped {
         node_center: Pos2,
         node_size: f32,
@@ -29,10 +48,33 @@ impl Default for EdgeShapeProps {
     }
 }
 
-#[derive(Default)]
 pub struct TipProps {
     pub size: f32,
     pub angle: f32,
+    pub placement: ArrowPlacement,
+    /// Adds a tip pointing to the start of the edge
+    pub at_start: bool,
+}
+
+impl Default for TipProps {
+    fn default() -> Self {
+        Self {
+            size: f32::default(),
+            angle: f32::default(),
+            placement: ArrowPlacement::End,
+            at_start: false,
+        }
+    }
+}
+
+impl TipProps {
+    /// Returns triangle of the arrow pointing in `dir` with the tip at `end`.
+    fn points(&self, end: Pos2, dir: Vec2) -> Vec<Pos2> {
+        let arrow_tip_dir_1 = rotate_vector(dir, self.angle) * self.size;
+        let arrow_tip_dir_2 = rotate_vector(dir, -self.angle) * self.size;
+
+        vec![end, end - arrow_tip_dir_1, end - arrow_tip_dir_2]
+    }
 }
 
 #[derive(Default)]
@@ -40,6 +82,8 @@ pub struct EdgeShapeBuilder<'a> {
     shape_props: EdgeShapeProps,
     tip: Option<&'a TipProps>,
     stroke: Stroke,
+    cap: Option<EdgeCap>,
+    gradient: Option<(Color32, Color32)>,
     scaler: Option<&'a Metadata>,
 }
 
@@ -67,6 +111,36 @@ impl<'a> EdgeShapeBuilder<'a> {
         self
     }
 
+    /// Quadratic curve through the control point shifted by `offset` perpendicular to the middle of the chord.
+    pub fn bowed(mut self, bounds: (Pos2, Pos2), offset: f32) -> Self {
+        self.shape_props = EdgeShapeProps::Bowed { bounds, offset };
+
+        self
+    }
+
+    pub fn bundled(mut self, bounds: (Pos2, Pos2), control_points: [Pos2; 2]) -> Self {
+        self.shape_props = EdgeShapeProps::Bundled {
+            bounds,
+            control_points,
+        };
+
+        self
+    }
+
+    /// Smooth curve leaving the start and entering the end along the axis.
+    pub fn bezier(mut self, bounds: (Pos2, Pos2), axis: Vec2) -> Self {
+        self.shape_props = EdgeShapeProps::Bezier { bounds, axis };
+
+        self
+    }
+
+    /// Polyline of segments parallel and perpendicular to the axis, turning in the middle between the ends.
+    pub fn orthogonal(mut self, bounds: (Pos2, Pos2), axis: Vec2) -> Self {
+        self.shape_props = EdgeShapeProps::Orthogonal { bounds, axis };
+
+        self
+    }
+
     pub fn looped(
         mut self,
         node_center: Pos2,
@@ -96,54 +170,115 @@ impl<'a> EdgeShapeBuilder<'a> {
         self
     }
 
+    pub fn with_cap(mut self, cap: EdgeCap) -> Self {
+        self.cap = Some(cap);
+
+        self
+    }
+
+    /// Colors the line with a gradient from the first color at the start to the second one at the end
+    /// instead of the stroke color. Tips take the color of the line where they are placed.
+    pub fn with_gradient(mut self, gradient: Option<(Color32, Color32)>) -> Self {
+        self.gradient = gradient;
+
+        self
+    }
+
+    /// Returns color of the line at `t` of its length from the start.
+    fn color_at(&self, stroke: Stroke, t: f32) -> Color32 {
+        self.gradient
+            .map_or(stroke.color, |(from, to)| from.lerp_to_gamma(to, t))
+    }
+
+    /// Returns shapes covering line ends with half circles if round caps are enabled.
+    fn caps(&self, ends: [Pos2; 2], stroke: Stroke) -> Vec<Shape> {
+        if self.cap != Some(EdgeCap::Round) {
+            return vec![];
+        }
+
+        [(ends[0], 0.), (ends[1], 1.)]
+            .into_iter()
+            .map(|(p, t)| Shape::circle_filled(p, stroke.width / 2., self.color_at(stroke, t)))
+            .collect()
+    }
+
+    /// Returns the line through the points, split into gradient segments if the gradient is set.
+    fn line(&self, points: Vec<Pos2>, stroke: Stroke) -> Vec<Shape> {
+        match self.gradient {
+            Some(gradient) => gradient_segments(&points, stroke.width, gradient),
+            None => vec![PathShape::line(points, stroke).into()],
+        }
+    }
+
+    /// Returns tip triangles colored as the line at their positions.
+    fn tips(&self, tips: Vec<(Vec<Pos2>, f32)>, stroke: Stroke) -> Vec<Shape> {
+        tips.into_iter()
+            .map(|(tip, t)| Shape::convex_polygon(tip, self.color_at(stroke, t), Stroke::default()))
+            .collect()
+    }
+
     pub fn shape_straight(&self, bounds: (Pos2, Pos2)) -> Vec<Shape> {
-        let mut res = vec![];
+        let (start, end) = bounds;
+        self.shape_polyline(vec![start, end])
+    }
 
+    fn shape_orthogonal(&self, bounds: (Pos2, Pos2), axis: Vec2) -> Vec<Shape> {
+        self.shape_polyline(orthogonal_points(bounds, axis))
+    }
+
+    fn shape_bezier(&self, bounds: (Pos2, Pos2), axis: Vec2) -> Vec<Shape> {
         let (start, end) = bounds;
-        let mut stroke = self.stroke;
+        let handle = ((end - start).dot(axis).abs() / 2.).max(MIN_BEZIER_HANDLE);
+        let cp_start = start + axis * handle;
+        let cp_end = end - axis * handle;
 
-        let mut points_line = vec![start, end];
-        let mut points_tip = match self.tip {
-            Some(tip_props) => {
-                let tip_dir = (end - start).normalized();
+        self.shape_cubic([start, cp_start, cp_end, end], cp_end)
+    }
 
-                let arrow_tip_dir_1 = rotate_vector(tip_dir, tip_props.angle) * tip_props.size;
-                let arrow_tip_dir_2 = rotate_vector(tip_dir, -tip_props.angle) * tip_props.size;
+    /// Builds line through the points with tips placed along it.
+    fn shape_polyline(&self, points: Vec<Pos2>) -> Vec<Shape> {
+        let mut res = vec![];
+        let mut stroke = self.stroke;
 
-                let tip_start_1 = end - arrow_tip_dir_1;
-                let tip_start_2 = end - arrow_tip_dir_2;
+        let mut points_line = points;
+        let mut tips = vec![];
+        if let Some(tip_props) = self.tip {
+            if tip_props.placement.at_middle() {
+                let (middle, tip_dir) = polyline_middle(&points_line);
+                tips.push((
+                    tip_props.points(middle + tip_dir * tip_props.size / 2., tip_dir),
+                    0.5,
+                ));
+            }
+            if tip_props.placement.at_end() {
+                let last = points_line.len() - 1;
+                let end = points_line[last];
+                let tip_dir = (end - points_line[last - 1]).normalized();
+                tips.push((tip_props.points(end, tip_dir), 1.));
 
                 // replace end of an edge with start of tip
-                *points_line.get_mut(1).unwrap() = end - tip_props.size * tip_dir;
-
-                vec![end, tip_start_1, tip_start_2]
+                points_line[last] = end - tip_props.size * tip_dir;
             }
-            None => vec![],
-        };
+            if tip_props.at_start {
+                let start = points_line[0];
+                let tip_dir = (start - points_line[1]).normalized();
+                tips.push((tip_props.points(start, tip_dir), 0.));
+                points_line[0] = start - tip_props.size * tip_dir;
+            }
+        }
 
         if let Some(scaler) = self.scaler {
             stroke.width = scaler.canvas_to_screen_size(stroke.width);
-            points_line = points_line
-                .iter()
-                .map(|p| scaler.canvas_to_screen_pos(*p))
-                .collect();
-            points_tip = points_tip
-                .iter()
-                .map(|p| scaler.canvas_to_screen_pos(*p))
-                .collect();
+            scale_points(&mut points_line, scaler);
+            for (tip, _) in &mut tips {
+                scale_points(tip, scaler);
+            }
         }
 
-        res.push(Shape::line_segment(
-            [points_line[0], points_line[1]],
-            stroke,
-        ));
-        if !points_tip.is_empty() {
-            res.push(Shape::convex_polygon(
-                points_tip,
-                stroke.color,
-                Stroke::default(),
-            ));
-        }
+        let ends = [points_line[0], points_line[points_line.len() - 1]];
+        res.extend(self.line(points_line, stroke));
+        res.extend(self.caps(ends, stroke));
+        res.extend(self.tips(tips, stroke));
 
         res
     }
@@ -192,13 +327,12 @@ impl<'a> EdgeShapeBuilder<'a> {
             )
             .into(),
         );
+        res.extend(self.caps([edge_end, edge_start], stroke));
         res
     }
 
     fn shape_curved(&self, bounds: (Pos2, Pos2), curve_size: f32, order: usize) -> Vec<Shape> {
-        let mut res = vec![];
         let (start, end) = bounds;
-        let mut stroke = self.stroke;
 
         let dist = end - start;
         let dir = dist.normalized();
@@ -210,59 +344,83 @@ impl<'a> EdgeShapeBuilder<'a> {
         let cp_start = cp - dir * curve_size / (order as f32 * dist * 0.5);
         let cp_end = cp + dir * curve_size / (order as f32 * dist * 0.5);
 
-        let mut points_curve = vec![start, cp_start, cp_end, end];
+        self.shape_cubic([start, cp_start, cp_end, end], cp)
+    }
 
-        let mut points_tip = match self.tip {
-            Some(tip_props) => {
-                let tip_dir = (end - cp).normalized();
+    fn shape_bowed(&self, (start, end): (Pos2, Pos2), offset: f32) -> Vec<Shape> {
+        let dir = (end - start).normalized();
+        let dir_p = Vec2::new(-dir.y, dir.x);
+        let cp = start + (end - start) / 2. + dir_p * offset;
+
+        // quadratic curve expressed as a cubic one
+        let cp_start = start + (cp - start) * 2. / 3.;
+        let cp_end = end + (cp - end) * 2. / 3.;
+
+        self.shape_cubic([start, cp_start, cp_end, end], cp)
+    }
+
+    /// Builds cubic bezier curve with the tip pointing from `tip_from` to the end of the curve.
+    fn shape_cubic(&self, points: [Pos2; 4], tip_from: Pos2) -> Vec<Shape> {
+        let mut res = vec![];
+        let end = points[3];
+        let mut stroke = self.stroke;
 
-                let arrow_tip_dir_1 = rotate_vector(tip_dir, tip_props.angle) * tip_props.size;
-                let arrow_tip_dir_2 = rotate_vector(tip_dir, -tip_props.angle) * tip_props.size;
+        let mut points_curve = points.to_vec();
 
-                let tip_start_1 = end - arrow_tip_dir_1;
-                let tip_start_2 = end - arrow_tip_dir_2;
+        let mut tips = vec![];
+        if let Some(tip_props) = self.tip {
+            if tip_props.placement.at_end() {
+                let tip_dir = (end - tip_from).normalized();
+                tips.push((tip_props.points(end, tip_dir), 1.));
 
                 // replace end of an edge with start of tip
                 *points_curve.get_mut(3).unwrap() = end - tip_props.size * tip_dir;
-
-                vec![end, tip_start_1, tip_start_2]
             }
-            None => vec![],
-        };
+            if tip_props.at_start {
+                let start = points[0];
+                let tip_dir = (start - points[1]).normalized();
+                tips.push((tip_props.points(start, tip_dir), 0.));
+                points_curve[0] = start - tip_props.size * tip_dir;
+            }
+            if tip_props.placement.at_middle() {
+                let (middle, tangent) = cubic_middle(points);
+                let tip_dir = tangent.normalized();
+                tips.push((
+                    tip_props.points(middle + tip_dir * tip_props.size / 2., tip_dir),
+                    0.5,
+                ));
+            }
+        }
 
         if let Some(scaler) = self.scaler {
             stroke.width = scaler.canvas_to_screen_size(stroke.width);
-            points_curve = points_curve
-                .iter()
-                .map(|p| scaler.canvas_to_screen_pos(*p))
-                .collect();
-            points_tip = points_tip
-                .iter()
-                .map(|p| scaler.canvas_to_screen_pos(*p))
-                .collect();
+            scale_points(&mut points_curve, scaler);
+            for (tip, _) in &mut tips {
+                scale_points(tip, scaler);
+            }
         }
 
-        res.push(
-            CubicBezierShape::from_points_stroke(
-                [
-                    points_curve[0],
-                    points_curve[1],
-                    points_curve[2],
-                    points_curve[3],
-                ],
-                false,
-                Color32::default(),
-                stroke,
-            )
-            .into(),
+        let curve = CubicBezierShape::from_points_stroke(
+            [
+                points_curve[0],
+                points_curve[1],
+                points_curve[2],
+                points_curve[3],
+            ],
+            false,
+            Color32::default(),
+            stroke,
         );
-        if !points_tip.is_empty() {
-            res.push(Shape::convex_polygon(
-                points_tip,
-                stroke.color,
-                Stroke::default(),
-            ));
+        if self.gradient.is_some() {
+            let points = (0..=GRADIENT_SEGMENTS)
+                .map(|i| curve.sample(i as f32 / GRADIENT_SEGMENTS as f32))
+                .collect();
+            res.extend(self.line(points, stroke));
+        } else {
+            res.push(curve.into());
         }
+        res.extend(self.caps([points_curve[0], points_curve[3]], stroke));
+        res.extend(self.tips(tips, stroke));
 
         res
     }
@@ -281,8 +439,111 @@ impl<'a> EdgeShapeBuilder<'a> {
                 curve_size,
                 order,
             } => self.shape_curved(bounds, curve_size, order),
+            EdgeShapeProps::Bowed { bounds, offset } => self.shape_bowed(bounds, offset),
+            EdgeShapeProps::Bundled {
+                bounds: (start, end),
+                control_points: [cp_start, cp_end],
+            } => self.shape_cubic([start, cp_start, cp_end, end], cp_end),
+            EdgeShapeProps::Bezier { bounds, axis } => self.shape_bezier(bounds, axis),
+            EdgeShapeProps::Orthogonal { bounds, axis } => self.shape_orthogonal(bounds, axis),
+        }
+    }
+}
+
+/// Minimal distance of bezier control points from the ends, keeps the tip direction defined.
+const MIN_BEZIER_HANDLE: f32 = 1.;
+/// Number of segments gradient edges are split into.
+const GRADIENT_SEGMENTS: usize = 16;
+
+/// Splits the polyline into about [`GRADIENT_SEGMENTS`] segments of equal length colored
+/// by interpolating the colors along the polyline.
+fn gradient_segments(points: &[Pos2], width: f32, (from, to): (Color32, Color32)) -> Vec<Shape> {
+    let total = points.windows(2).map(|w| w[0].distance(w[1])).sum::<f32>();
+    if total <= 0. {
+        return vec![];
+    }
+
+    let step = total / GRADIENT_SEGMENTS as f32;
+    let mut res = vec![];
+    let mut passed = 0.;
+    for w in points.windows(2) {
+        let len = w[0].distance(w[1]);
+        let pieces = (len / step).ceil().max(1.) as usize;
+        for i in 0..pieces {
+            let (a, b) = (i as f32 / pieces as f32, (i + 1) as f32 / pieces as f32);
+            let t = (passed + len * (a + b) / 2.) / total;
+            let stroke = Stroke::new(width, from.lerp_to_gamma(to, t));
+            res.push(Shape::line_segment(
+                [w[0].lerp(w[1], a), w[0].lerp(w[1], b)],
+                stroke,
+            ));
+        }
+        passed += len;
+    }
+
+    res
+}
+
+/// Returns the unit axis, horizontal or vertical, along which the offset between the ends is larger.
+pub fn routing_axis(start: Pos2, end: Pos2) -> Vec2 {
+    let delta = end - start;
+    if delta.x.abs() >= delta.y.abs() {
+        Vec2::X * if delta.x < 0. { -1. } else { 1. }
+    } else {
+        Vec2::Y * if delta.y < 0. { -1. } else { 1. }
+    }
+}
+
+/// Returns points of the Z shaped orthogonal route between the ends leaving the start along the axis.
+pub fn orthogonal_points((start, end): (Pos2, Pos2), axis: Vec2) -> Vec<Pos2> {
+    let middle = start + (end - start) / 2.;
+    if axis.x == 0. {
+        vec![
+            start,
+            Pos2::new(start.x, middle.y),
+            Pos2::new(end.x, middle.y),
+            end,
+        ]
+    } else {
+        vec![
+            start,
+            Pos2::new(middle.x, start.y),
+            Pos2::new(middle.x, end.y),
+            end,
+        ]
+    }
+}
+
+/// Returns point in the middle of the polyline length and direction of the polyline there.
+fn polyline_middle(points: &[Pos2]) -> (Pos2, Vec2) {
+    let total = points.windows(2).map(|w| w[0].distance(w[1])).sum::<f32>();
+    let mut left = total / 2.;
+    for w in points.windows(2) {
+        let len = w[0].distance(w[1]);
+        if len > 0. && left <= len {
+            let dir = (w[1] - w[0]) / len;
+            return (w[0] + dir * left, dir);
         }
+        left -= len;
     }
+
+    let (first, last) = (points[0], points[points.len() - 1]);
+    (first + (last - first) / 2., (last - first).normalized())
+}
+
+/// Converts canvas points to screen coordinates in place.
+fn scale_points(points: &mut [Pos2], scaler: &Metadata) {
+    for p in points {
+        *p = scaler.canvas_to_screen_pos(*p);
+    }
+}
+
+/// Returns point and tangent of the cubic bezier curve at its parametric middle `t = 0.5`.
+fn cubic_middle([p0, p1, p2, p3]: [Pos2; 4]) -> (Pos2, Vec2) {
+    let point = (p0.to_vec2() + 3. * p1.to_vec2() + 3. * p2.to_vec2() + p3.to_vec2()) / 8.;
+    let tangent = 0.75 * ((p1 - p0) + 2. * (p2 - p1) + (p3 - p2));
+
+    (point.to_pos2(), tangent)
 }
 
 /// rotates vector by angle
@@ -291,3 +552,140 @@ fn rotate_vector(vec: Vec2, angle: f32) -> Vec2 {
     let sin = angle.sin();
     Vec2::new(cos * vec.x - sin * vec.y, sin * vec.x + cos * vec.y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::epaint::ColorMode;
+
+    #[test]
+    fn test_cubic_middle() {
+        let (point, tangent) = cubic_middle([
+            Pos2::new(0., 0.),
+            Pos2::new(0., 10.),
+            Pos2::new(20., 10.),
+            Pos2::new(20., 0.),
+        ]);
+
+        assert_eq!(point, Pos2::new(10., 7.5));
+        assert_eq!(tangent.normalized(), Vec2::X);
+    }
+
+    #[test]
+    fn test_gradient() {
+        let shapes = EdgeShapeBuilder::new(Stroke::new(1., Color32::WHITE))
+            .straight((Pos2::new(0., 0.), Pos2::new(160., 0.)))
+            .with_gradient(Some((Color32::BLACK, Color32::WHITE)))
+            .build();
+
+        let colors = shapes
+            .iter()
+            .map(|s| match s {
+                Shape::LineSegment { stroke, .. } => match stroke.color {
+                    ColorMode::Solid(color) => color,
+                    ColorMode::UV(_) => panic!("invalid color mode"),
+                },
+                _ => panic!("invalid shape type"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(colors.len(), GRADIENT_SEGMENTS);
+        assert!(colors.windows(2).all(|w| w[0].r() < w[1].r()));
+    }
+
+    #[test]
+    fn test_bowed() {
+        let middle = |offset| {
+            let shapes = EdgeShapeBuilder::new(Stroke::new(1., Color32::WHITE))
+                .bowed((Pos2::new(0., 0.), Pos2::new(20., 0.)), offset)
+                .build();
+            let Some(Shape::CubicBezier(curve)) = shapes.first() else {
+                panic!("invalid shape type")
+            };
+            cubic_middle(curve.points).0
+        };
+
+        assert_eq!(middle(0.), Pos2::new(10., 0.));
+        // the middle of a quadratic curve is halfway to its control point
+        assert_eq!(middle(10.), Pos2::new(10., 5.));
+        assert_eq!(middle(-10.), Pos2::new(10., -5.));
+    }
+
+    #[test]
+    fn test_arrow_placement() {
+        let tip = |placement| TipProps {
+            size: 2.,
+            angle: 0.5,
+            placement,
+            at_start: false,
+        };
+        let shapes = |tip_props| {
+            EdgeShapeBuilder::new(Stroke::new(1., Color32::WHITE))
+                .straight((Pos2::ZERO, Pos2::new(10., 0.)))
+                .with_tip(&tip_props)
+                .build()
+        };
+
+        let middle = shapes(tip(ArrowPlacement::Middle));
+        assert_eq!(middle.len(), 2);
+        assert!(matches!(&middle[0], Shape::Path(p) if p.points[1] == Pos2::new(10., 0.)));
+        assert!(matches!(&middle[1], Shape::Path(p) if p.points[0] == Pos2::new(6., 0.)));
+
+        assert_eq!(shapes(tip(ArrowPlacement::Both)).len(), 3);
+
+        let both_ends = shapes(TipProps {
+            at_start: true,
+            ..tip(ArrowPlacement::End)
+        });
+        assert_eq!(both_ends.len(), 3);
+        assert!(
+            matches!(&both_ends[0], Shape::Path(p) if p.points == vec![Pos2::new(2., 0.), Pos2::new(8., 0.)])
+        );
+        assert!(matches!(&both_ends[2], Shape::Path(p) if p.points[0] == Pos2::ZERO));
+    }
+
+    #[test]
+    fn test_orthogonal_route() {
+        let (start, end) = (Pos2::new(0., 0.), Pos2::new(10., 4.));
+        let axis = routing_axis(start, end);
+        assert_eq!(axis, Vec2::X);
+
+        let points = orthogonal_points((start, end), axis);
+        assert_eq!(
+            points,
+            vec![start, Pos2::new(5., 0.), Pos2::new(5., 4.), end]
+        );
+        assert_eq!(polyline_middle(&points), (Pos2::new(5., 2.), Vec2::Y));
+        assert_eq!(routing_axis(end, Pos2::new(8., -10.)), -Vec2::Y);
+
+        let tip_props = TipProps {
+            size: 2.,
+            angle: 0.5,
+            placement: ArrowPlacement::End,
+            at_start: false,
+        };
+        let shapes = EdgeShapeBuilder::new(Stroke::new(1., Color32::WHITE))
+            .orthogonal((start, end), axis)
+            .with_tip(&tip_props)
+            .build();
+        // the last segment is shortened by the tip pointing along the axis
+        assert!(matches!(&shapes[0], Shape::Path(p) if p.points[3] == Pos2::new(8., 4.)));
+        assert!(matches!(&shapes[1], Shape::Path(p) if p.points[0] == end));
+    }
+
+    #[test]
+    fn test_round_caps() {
+        let shapes = |cap| {
+            EdgeShapeBuilder::new(Stroke::new(4., Color32::WHITE))
+                .curved((Pos2::ZERO, Pos2::new(10., 0.)), 5., 1)
+                .with_cap(cap)
+                .build()
+        };
+
+        assert_eq!(shapes(EdgeCap::Butt).len(), 1);
+        let round = shapes(EdgeCap::Round);
+        assert_eq!(round.len(), 3);
+        assert!(
+            matches!(&round[2], Shape::Circle(c) if c.center == Pos2::new(10., 0.) && c.radius == 2.)
+        );
+    }
+}
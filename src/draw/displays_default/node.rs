@@ -1,10 +1,10 @@
 use egui::{
-    epaint::{CircleShape, TextShape},
-    Color32, FontFamily, FontId, Pos2, Shape, Stroke, Vec2,
+    epaint::{CircleShape, TextShape, Vertex},
+    Color32, FontFamily, FontId, Mesh, Pos2, Shape, Stroke, TextureId, Vec2,
 };
 use petgraph::{stable_graph::IndexType, EdgeType};
 
-use crate::{draw::drawer::DrawContext, DisplayNode, NodeProps};
+use crate::{draw::drawer::DrawContext, Badge, BadgePosition, DisplayNode, NodeProps};
 
 /// This is the default node shape which is used to display nodes in the graph.
 ///
@@ -15,12 +15,20 @@ pub struct DefaultNodeShape {
 
     pub selected: bool,
     pub dragged: bool,
+    pub hovered: bool,
     pub color: Option<Color32>,
+    pub opacity: f32,
+    /// Image drawn inside the circle, with the circle color used as its border unless node stroke is set
+    pub texture: Option<TextureId>,
+    /// Badge drawn in a corner of the circle
+    pub badge: Option<Badge>,
 
     pub label_text: String,
 
     /// Shape dependent property
     pub radius: f32,
+    /// Multiplier of sizes in canvas units, `1 / zoom` when nodes keep constant screen size
+    pub size_scale: f32,
 }
 
 impl<N: Clone> From<NodeProps<N>> for DefaultNodeShape {
@@ -29,10 +37,15 @@ impl<N: Clone> From<NodeProps<N>> for DefaultNodeShape {
             pos: node_props.location(),
             selected: node_props.selected,
             dragged: node_props.dragged,
+            hovered: node_props.hovered(),
             label_text: node_props.label.to_string(),
             color: node_props.color(),
+            opacity: node_props.opacity(),
+            texture: node_props.texture(),
+            badge: node_props.badge().cloned(),
 
             radius: 5.0,
+            size_scale: node_props.size_scale(),
         }
     }
 }
@@ -41,39 +54,69 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType> DisplayNode<N, E, Ty, Ix>
     for DefaultNodeShape
 {
     fn is_inside(&self, pos: Pos2) -> bool {
-        is_inside_circle(self.pos, self.radius, pos)
+        is_inside_circle(self.pos, self.radius * self.size_scale, pos)
     }
 
     fn closest_boundary_point(&self, dir: Vec2) -> Pos2 {
-        closest_point_on_circle(self.pos, self.radius, dir)
+        closest_point_on_circle(self.pos, self.radius * self.size_scale, dir)
     }
 
     fn shapes(&mut self, ctx: &DrawContext) -> Vec<Shape> {
         let mut res = Vec::with_capacity(2);
 
-        let is_interacted = self.selected || self.dragged;
-
-        let style = if is_interacted {
-            ctx.ctx.style().visuals.widgets.active
+        let style = if self.selected || self.dragged {
+            ctx.visuals.widgets.active
+        } else if self.hovered {
+            ctx.visuals.widgets.hovered
         } else {
-            ctx.ctx.style().visuals.widgets.inactive
+            ctx.visuals.widgets.inactive
         };
 
-        let color = if let Some(c) = self.color {
-            c
-        } else {
-            style.fg_stroke.color
-        };
+        let (state_color, mut stroke) =
+            ctx.style
+                .node_appearance(self.selected, self.dragged, self.hovered);
+        let color = state_color
+            .or(self.color)
+            .unwrap_or(style.fg_stroke.color)
+            .gamma_multiply(self.opacity);
 
         let circle_center = ctx.meta.canvas_to_screen_pos(self.pos);
-        let circle_radius = ctx.meta.canvas_to_screen_size(self.radius);
-        let circle_shape = CircleShape {
-            center: circle_center,
-            radius: circle_radius,
-            fill: color,
-            stroke: Stroke::default(),
-        };
-        res.push(circle_shape.into());
+        let circle_radius = self.screen_size(ctx, self.radius);
+        stroke.width = self.screen_size(ctx, stroke.width);
+
+        let glow = ctx
+            .style
+            .node_glow(self.selected, self.dragged, self.hovered);
+        if let Some(glow) = glow {
+            let width = self.screen_size(ctx, GLOW_WIDTH);
+            for i in (1..=GLOW_LAYERS).rev() {
+                let radius = circle_radius + width * i as f32 / GLOW_LAYERS as f32;
+                let color = glow.gamma_multiply(GLOW_LAYER_OPACITY * self.opacity);
+                res.push(CircleShape::filled(circle_center, radius, color).into());
+            }
+        }
+        stroke.color = stroke.color.gamma_multiply(self.opacity);
+
+        if let Some(texture) = self.texture {
+            let tint = Color32::WHITE.gamma_multiply(self.opacity);
+            res.push(textured_circle(circle_center, circle_radius, texture, tint).into());
+            if stroke.is_empty() {
+                stroke = Stroke::new(self.screen_size(ctx, TEXTURE_BORDER), color);
+            }
+            res.push(CircleShape::stroke(circle_center, circle_radius, stroke).into());
+        } else {
+            let circle_shape = CircleShape {
+                center: circle_center,
+                radius: circle_radius,
+                fill: color,
+                stroke,
+            };
+            res.push(circle_shape.into());
+        }
+
+        if let Some(badge) = &self.badge {
+            res.extend(self.badge_shapes(ctx, badge, circle_center, circle_radius));
+        }
 
         let label_visible = ctx.style.labels_always || self.selected || self.dragged;
         if !label_visible {
@@ -104,11 +147,108 @@ impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType> DisplayNode<N, E, Ty, Ix>
         self.pos = state.location();
         self.selected = state.selected;
         self.dragged = state.dragged;
+        self.hovered = state.hovered();
         self.label_text = state.label.to_string();
         self.color = state.color();
+        self.opacity = state.opacity();
+        self.texture = state.texture();
+        self.badge = state.badge().cloned();
+        self.size_scale = state.size_scale();
+    }
+}
+
+impl DefaultNodeShape {
+    /// Converts size in canvas units to screen points, keeping it constant if nodes do not scale with zoom.
+    fn screen_size(&self, ctx: &DrawContext, size: f32) -> f32 {
+        ctx.meta.canvas_to_screen_size(size * self.size_scale)
+    }
+
+    fn badge_shapes(
+        &self,
+        ctx: &DrawContext,
+        badge: &Badge,
+        circle_center: Pos2,
+        circle_radius: f32,
+    ) -> [Shape; 2] {
+        let radius = badge_radius(circle_radius);
+        let [r, g, b, _] = badge.color.to_array();
+        let luma = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+        let text_color = if luma > 128. {
+            Color32::BLACK
+        } else {
+            Color32::WHITE
+        };
+        let galley = ctx.ctx.fonts(|f| {
+            f.layout_no_wrap(
+                badge.text.clone(),
+                FontId::new(radius * 1.4, FontFamily::Proportional),
+                text_color.gamma_multiply(self.opacity),
+            )
+        });
+
+        // long texts widen the circle instead of overflowing it
+        let radius = radius.max(galley.size().x / 2. + 1.);
+        let center = badge_center(circle_center, circle_radius, badge.position);
+        let circle = CircleShape::filled(center, radius, badge.color.gamma_multiply(self.opacity));
+        let text = TextShape::new(center - galley.size() / 2., galley, text_color);
+
+        [circle.into(), text.into()]
     }
 }
 
+/// Width in canvas units of the border around node images.
+const TEXTURE_BORDER: f32 = 1.;
+/// Width in canvas units of the halo around highlighted nodes.
+const GLOW_WIDTH: f32 = 4.;
+/// Number of translucent circles the halo is made of, overlapping circles make it fade out to the edge.
+const GLOW_LAYERS: usize = 4;
+/// Opacity of a single halo circle.
+const GLOW_LAYER_OPACITY: f32 = 0.2;
+/// Radius of badges relative to the radius of the node.
+const BADGE_SCALE: f32 = 0.5;
+/// Minimal radius of badges in screen points, so they stay readable when zoomed out.
+const BADGE_MIN_RADIUS: f32 = 6.;
+/// Number of segments of the circle node images are clipped to.
+const TEXTURE_SEGMENTS: usize = 32;
+
+/// Builds a triangle fan covering the circle with the texture fitted to the bounding square of the circle.
+fn textured_circle(center: Pos2, radius: f32, texture: TextureId, tint: Color32) -> Mesh {
+    let mut mesh = Mesh::with_texture(texture);
+    let vertex = |pos: Pos2| Vertex {
+        pos,
+        uv: Pos2::new(0.5, 0.5) + (pos - center) / (2. * radius),
+        color: tint,
+    };
+
+    mesh.vertices.push(vertex(center));
+    for i in 0..TEXTURE_SEGMENTS {
+        let dir = Vec2::angled(std::f32::consts::TAU * i as f32 / TEXTURE_SEGMENTS as f32);
+        mesh.vertices.push(vertex(center + dir * radius));
+    }
+    for i in 0..TEXTURE_SEGMENTS {
+        let next = (i + 1) % TEXTURE_SEGMENTS;
+        mesh.add_triangle(0, i as u32 + 1, next as u32 + 1);
+    }
+
+    mesh
+}
+
+/// Radius of the badge in screen points for the node circle of the given radius in screen points.
+fn badge_radius(circle_radius: f32) -> f32 {
+    (circle_radius * BADGE_SCALE).max(BADGE_MIN_RADIUS)
+}
+
+/// Center of the badge, placed on the circle boundary at the diagonal towards the corner.
+fn badge_center(center: Pos2, radius: f32, position: BadgePosition) -> Pos2 {
+    let corner = match position {
+        BadgePosition::TopLeft => Vec2::new(-1., -1.),
+        BadgePosition::TopRight => Vec2::new(1., -1.),
+        BadgePosition::BottomLeft => Vec2::new(-1., 1.),
+        BadgePosition::BottomRight => Vec2::new(1., 1.),
+    };
+    closest_point_on_circle(center, radius, corner)
+}
+
 fn closest_point_on_circle(center: Pos2, radius: f32, dir: Vec2) -> Pos2 {
     center + dir.normalized() * radius
 }
@@ -121,7 +261,94 @@ fn is_inside_circle(center: Pos2, radius: f32, pos: Pos2) -> bool {
 #[cfg(test)]
 mod test {
     use super::*;
-    use egui::Pos2;
+    use crate::export::shapes;
+    use crate::test_util::graph;
+    use crate::{Graph, Metadata, SettingsStyle};
+    use egui::{Context, Pos2};
+
+    /// First drawn node.
+    fn circle(g: &Graph, style: &SettingsStyle, meta: &Metadata) -> CircleShape {
+        shapes(&Context::default(), g, style, meta)
+            .into_iter()
+            .find_map(|s| match s {
+                Shape::Circle(c) => Some(c),
+                _ => None,
+            })
+            .expect("node is not drawn as circle")
+    }
+
+    #[test]
+    fn test_node_stroke_scales_with_zoom() {
+        let mut g = graph();
+        g.g.node_weights_mut().for_each(|n| n.set_selected(true));
+
+        let mut meta = Metadata::default();
+        meta.zoom = 2.;
+        let style = SettingsStyle::default()
+            .with_node_stroke(Stroke::new(1., Color32::RED))
+            .with_node_stroke_selected(Stroke::new(3., Color32::BLUE));
+
+        assert_eq!(
+            circle(&g, &style, &meta).stroke,
+            Stroke::new(6., Color32::BLUE)
+        );
+    }
+
+    #[test]
+    fn test_fixed_node_screen_size() {
+        let g = graph();
+        let mut meta = Metadata::default();
+        meta.zoom = 4.;
+
+        assert_eq!(circle(&g, &SettingsStyle::default(), &meta).radius, 20.);
+        assert_eq!(
+            circle(
+                &g,
+                &SettingsStyle::default().with_fixed_node_screen_size(true),
+                &meta
+            )
+            .radius,
+            5.
+        );
+    }
+
+    #[test]
+    fn test_fixed_node_screen_size_hit_area() {
+        let mut g = graph();
+        let style = SettingsStyle::default().with_fixed_node_screen_size(true);
+        // hit tests and edges use node boundaries before the node is drawn
+        g.sync_node_size_scale(style.node_size_scale(4.));
+
+        let n = g.node(petgraph::stable_graph::NodeIndex::new(0)).unwrap();
+        assert_eq!(crate::node_size(n, Vec2::new(1., 0.)), 1.25);
+    }
+
+    #[test]
+    fn test_textured_circle() {
+        let mesh = textured_circle(
+            Pos2::new(10., 10.),
+            5.,
+            TextureId::Managed(1),
+            Color32::WHITE,
+        );
+
+        assert!(mesh.is_valid());
+        assert_eq!(mesh.vertices.len(), TEXTURE_SEGMENTS + 1);
+        assert_eq!(mesh.vertices[0].uv, Pos2::new(0.5, 0.5));
+        // the first boundary vertex lies on the right edge of the texture
+        assert_eq!(mesh.vertices[1].uv, Pos2::new(1., 0.5));
+    }
+
+    #[test]
+    fn test_badge() {
+        assert_eq!(badge_radius(20.), 10.);
+        assert_eq!(badge_radius(2.), BADGE_MIN_RADIUS);
+
+        let center = badge_center(Pos2::new(0., 0.), 2_f32.sqrt(), BadgePosition::TopRight);
+        assert!((center - Pos2::new(1., -1.)).length() < 1e-5);
+        let center = badge_center(Pos2::new(0., 0.), 2_f32.sqrt(), BadgePosition::BottomLeft);
+        assert!((center - Pos2::new(-1., 1.)).length() < 1e-5);
+    }
 
     #[test]
     fn test_closest_point_on_circle() {
@@ -1,22 +1,36 @@
+use std::collections::{BTreeMap, HashSet};
 use std::marker::PhantomData;
 
-use egui::{Context, Painter, Shape};
-use petgraph::graph::IndexType;
+use egui::{Context, Painter, Pos2, Shape, Stroke, Vec2, Visuals};
+use petgraph::graph::{EdgeIndex, IndexType, NodeIndex};
 use petgraph::EdgeType;
 
 use crate::{
     layouts::{Layout, LayoutState},
+    node_size,
     settings::SettingsStyle,
-    Graph, Metadata,
+    Graph, Metadata, Node, ReciprocalEdges,
 };
 
-use super::{DisplayEdge, DisplayNode};
+use super::{
+    hull::{convex_hull, group_color},
+    DisplayEdge, DisplayNode,
+};
+
+/// Distance in canvas units between node boundaries and the hull of their group.
+const HULL_PADDING: f32 = 10.;
+/// Distance in canvas units between node boundary and the keyboard focus ring.
+const FOCUS_RING_PADDING: f32 = 3.;
+/// Number of boundary points sampled around every node of a group.
+const HULL_SAMPLES: usize = 8;
 
 /// Contains all the data about current widget state which is needed for custom drawing functions.
 pub struct DrawContext<'a> {
     pub ctx: &'a Context,
     pub painter: &'a Painter,
     pub style: &'a SettingsStyle,
+    /// Visuals colors of default displays are derived from, see [`SettingsStyle::with_follow_theme`].
+    pub visuals: &'a Visuals,
     pub is_directed: bool,
     pub meta: &'a Metadata,
 }
@@ -34,6 +48,7 @@ where
 {
     ctx: &'a DrawContext<'a>,
     g: &'a mut Graph<N, E, Ty, Ix, Nd, Ed>,
+    shapes: Vec<Shape>,
     delayed: Vec<Shape>,
 
     _marker: PhantomData<(Nd, Ed, L, S)>,
@@ -54,78 +69,323 @@ where
         Drawer {
             ctx,
             g,
+            shapes: Vec::new(),
             delayed: Vec::new(),
             _marker: PhantomData,
         }
     }
 
     pub fn draw(mut self) {
-        self.draw_edges();
-        self.draw_nodes();
-        self.draw_postponed();
+        if let Some(color) = self.ctx.style.background_color {
+            let rect = self.ctx.painter.clip_rect();
+            self.ctx.painter.rect_filled(rect, 0., color);
+        }
+        self.fill_shapes();
+        self.ctx.painter.extend(std::mem::take(&mut self.shapes));
+        self.draw_edge_creation_preview();
+    }
+
+    /// Returns shapes of all group hulls, edges and nodes in the order they are painted by [`Drawer::draw`].
+    pub(crate) fn shapes(mut self) -> Vec<Shape> {
+        self.fill_shapes();
+        self.shapes
+    }
+
+    fn fill_shapes(&mut self) {
+        let focus = self
+            .ctx
+            .meta
+            .focus
+            .map(|(idx, hops)| self.g.neighborhood(NodeIndex::new(idx), hops));
+
+        // the same order applies to interacted elements which are drawn on top of the rest
+        if self.ctx.style.edges_on_top {
+            self.draw_nodes(focus.as_ref());
+            self.draw_edges(focus.as_ref());
+        } else {
+            self.draw_edges(focus.as_ref());
+            self.draw_nodes(focus.as_ref());
+        }
+        self.shapes.append(&mut self.delayed);
+        self.shapes.extend(self.focus_ring());
+
+        // hulls are computed from updated node displays but painted behind everything else
+        let hulls = self.group_hulls();
+        self.shapes.splice(0..0, hulls);
     }
 
-    fn draw_postponed(&mut self) {
-        self.delayed.iter().for_each(|s| {
-            self.ctx.painter.add(s.clone());
+    /// Fills the convex hull around nodes of every group with a translucent color of the group.
+    fn group_hulls(&self) -> Vec<Shape> {
+        let mut groups: BTreeMap<&str, Vec<Pos2>> = BTreeMap::new();
+        self.g.nodes_iter().for_each(|(idx, n)| {
+            if self.g.collapsed_into(idx).is_some() {
+                return;
+            }
+            let Some(group) = n.group() else {
+                return;
+            };
+
+            let points = groups.entry(group).or_default();
+            for i in 0..HULL_SAMPLES {
+                let dir = Vec2::angled(std::f32::consts::TAU * i as f32 / HULL_SAMPLES as f32);
+                let boundary = n.display().closest_boundary_point(dir);
+                points.push(
+                    self.ctx
+                        .meta
+                        .canvas_to_screen_pos(boundary + dir * HULL_PADDING),
+                );
+            }
         });
+
+        groups
+            .into_iter()
+            .map(|(group, points)| {
+                Shape::convex_polygon(convex_hull(points), group_color(group), Stroke::NONE)
+            })
+            .collect()
     }
 
-    fn draw_nodes(&mut self) {
-        self.g
+    /// Draws a ring around the node focused with keyboard navigation.
+    fn focus_ring(&self) -> Option<Shape> {
+        let idx = self.ctx.meta.focused_node?;
+        let n = self.g.node(NodeIndex::new(idx))?;
+
+        let radius = node_size(n, Vec2::new(1., 0.)) + FOCUS_RING_PADDING;
+        Some(Shape::circle_stroke(
+            self.ctx.meta.canvas_to_screen_pos(n.location()),
+            self.ctx.meta.canvas_to_screen_size(radius),
+            self.ctx.visuals.selection.stroke,
+        ))
+    }
+
+    /// Draws a dashed line from the start node of the edge being created to the cursor.
+    /// If the cursor is over another node the edge would be created with, the line snaps to its boundary.
+    fn draw_edge_creation_preview(&self) {
+        let Some((start_idx, cursor)) = self.ctx.meta.edge_creation else {
+            return;
+        };
+        let Some(start) = self.g.node(NodeIndex::new(start_idx)) else {
+            return;
+        };
+
+        let end = self
+            .ctx
+            .meta
+            .edge_creation_end
+            .and_then(|idx| self.g.node(NodeIndex::new(idx)));
+
+        // the preview keeps the same gap to node boundaries as the edge it creates
+        let gap = self.ctx.style.edge_end_gap;
+        let (start_point, end_point) = if let Some(end) = end {
+            let dir = (end.location() - start.location()).normalized();
+            (
+                start.display().closest_boundary_point(dir) + dir * gap,
+                end.display().closest_boundary_point(-dir) - dir * gap,
+            )
+        } else {
+            let dir = (cursor - start.location()).normalized();
+            (
+                start.display().closest_boundary_point(dir) + dir * gap,
+                cursor,
+            )
+        };
+
+        let stroke = self.ctx.visuals.widgets.hovered.fg_stroke;
+        let points = [
+            self.ctx.meta.canvas_to_screen_pos(start_point),
+            self.ctx.meta.canvas_to_screen_pos(end_point),
+        ];
+        let dash_length = self.ctx.meta.canvas_to_screen_size(5.);
+        self.ctx.painter.extend(Shape::dashed_line(
+            &points,
+            stroke,
+            dash_length,
+            dash_length,
+        ));
+    }
+
+    /// Draws nodes, nodes outside of the `focus` set are dimmed.
+    fn draw_nodes(&mut self, focus: Option<&HashSet<NodeIndex<Ix>>>) {
+        let mut indices = self
+            .g
             .g
             .node_indices()
-            .collect::<Vec<_>>()
-            .into_iter()
-            .for_each(|idx| {
-                let n = self.g.node_mut(idx).unwrap();
-                let props = n.props().clone();
+            .filter(|idx| self.g.collapsed_into(*idx).is_none())
+            .collect::<Vec<_>>();
+        // stable sort keeps insertion order of nodes with the same priority
+        indices.sort_by_key(|idx| self.g.node(*idx).map_or(0, Node::z));
 
-                let display = n.display_mut();
-                display.update(&props);
-                let shapes = display.shapes(self.ctx);
+        for idx in indices {
+            let n = self.g.node_mut(idx).unwrap();
+            let mut props = n.props().clone();
+            if focus.is_some_and(|f| !f.contains(&idx)) {
+                props.dim(self.ctx.style.focus_dim);
+            }
 
-                if n.selected() || n.dragged() {
-                    for s in shapes {
-                        self.delayed.push(s);
-                    }
-                } else {
-                    for s in shapes {
-                        self.ctx.painter.add(s);
-                    }
-                }
-            });
+            let display = n.display_mut();
+            display.update(&props);
+            let shapes = display.shapes(self.ctx);
+
+            if emphasized(n) {
+                self.delayed.extend(shapes);
+            } else {
+                self.shapes.extend(shapes);
+            }
+        }
     }
 
-    fn draw_edges(&mut self) {
+    /// Returns whether the edge is hidden in favor of its opposite edge if there is one which is drawn together with it.
+    fn reciprocal(&self, idx: EdgeIndex<Ix>) -> Option<bool> {
+        if !self.ctx.is_directed || self.ctx.style.reciprocal_edges == ReciprocalEdges::Overlap {
+            return None;
+        }
+
+        let (start, end) = self.g.edge_endpoints(idx)?;
+        if start == end || self.g.edge(idx)?.order() != 0 {
+            return None;
+        }
+
+        let (opposite, _) = self
+            .g
+            .edges_connecting(end, start)
+            .find(|(_, e)| e.order() == 0)?;
+        let hidden = self.ctx.style.reciprocal_edges == ReciprocalEdges::Combined && opposite < idx;
+
+        Some(hidden)
+    }
+
+    /// Whether there is an edge going in the opposite direction between the endpoints of the edge.
+    fn mutual(&self, idx: EdgeIndex<Ix>) -> bool {
+        if !self.ctx.is_directed {
+            return false;
+        }
+
+        let Some((start, end)) = self.g.edge_endpoints(idx) else {
+            return false;
+        };
+
+        start != end
+            && self
+                .g
+                .edges_between(start, end)
+                .into_iter()
+                .any(|e| self.g.edge_endpoints(e) == Some((end, start)))
+    }
+
+    /// Draws edges, edges with an endpoint outside of the `focus` set are dimmed together with the endpoint.
+    fn draw_edges(&mut self, focus: Option<&HashSet<NodeIndex<Ix>>>) {
         self.g
             .g
             .edge_indices()
             .collect::<Vec<_>>()
             .into_iter()
             .for_each(|idx| {
-                let (idx_start, idx_end) = self.g.edge_endpoints(idx).unwrap();
+                // edges of collapsed nodes are drawn from the nodes they are collapsed into
+                let Some((idx_start, idx_end)) = self.g.visible_edge_endpoints(idx) else {
+                    return;
+                };
 
                 // FIXME: not a good decision to clone nodes for every edge
-                let start = self.g.node(idx_start).cloned().unwrap();
-                let end = self.g.node(idx_end).cloned().unwrap();
+                let mut start = self.g.node(idx_start).cloned().unwrap();
+                let mut end = self.g.node(idx_end).cloned().unwrap();
+                if let Some(focus) = focus {
+                    for (idx, n) in [(idx_start, &mut start), (idx_end, &mut end)] {
+                        if !focus.contains(&idx) {
+                            n.dim(self.ctx.style.focus_dim);
+                        }
+                    }
+                }
+
+                let reciprocal = self.reciprocal(idx);
+                if reciprocal == Some(true) {
+                    return;
+                }
+                let mutual = self.mutual(idx);
 
                 let e = self.g.edge_mut(idx).unwrap();
-                let props = e.props().clone();
+                let mut props = e.props().clone();
+                if reciprocal.is_some() {
+                    props.set_reciprocal(self.ctx.style.reciprocal_edges);
+                }
+                props.set_mutual(mutual);
 
                 let display = e.display_mut();
                 display.update(&props);
                 let shapes = display.shapes(&start, &end, self.ctx);
 
-                if e.selected() {
-                    for s in shapes {
-                        self.delayed.push(s);
-                    }
+                // edges of emphasized nodes are drawn on top together with them
+                if e.selected() || emphasized(&start) || emphasized(&end) {
+                    self.delayed.extend(shapes);
                 } else {
-                    for s in shapes {
-                        self.ctx.painter.add(s);
-                    }
+                    self.shapes.extend(shapes);
                 }
             });
     }
 }
+
+/// Whether the node is drawn on top of other nodes regardless of its z order.
+fn emphasized<N, E, Ty, Ix, D>(n: &Node<N, E, Ty, Ix, D>) -> bool
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    D: DisplayNode<N, E, Ty, Ix>,
+{
+    n.selected() || n.dragged()
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Context;
+    use petgraph::graph::NodeIndex;
+
+    use super::*;
+    use crate::export::shapes;
+    use crate::test_util::graph;
+    use crate::SettingsStyle;
+
+    #[test]
+    fn test_edges_on_top() {
+        let g = graph();
+        let ctx = Context::default();
+        let meta = Metadata::default();
+
+        let behind = shapes(&ctx, &g, &SettingsStyle::default(), &meta);
+        assert!(!matches!(behind.first(), Some(Shape::Circle(_))));
+        assert!(matches!(behind.last(), Some(Shape::Circle(_))));
+
+        let on_top = shapes(
+            &ctx,
+            &g,
+            &SettingsStyle::default().with_edges_on_top(true),
+            &meta,
+        );
+        assert!(matches!(on_top.first(), Some(Shape::Circle(_))));
+        assert!(!matches!(on_top.last(), Some(Shape::Circle(_))));
+    }
+
+    #[test]
+    fn test_reciprocal_edges() {
+        let mut g = graph();
+        g.add_edge(NodeIndex::new(1), NodeIndex::new(0), ());
+
+        let ctx = Context::default();
+        let meta = Metadata::default();
+        let count = |reciprocal, f: fn(&Shape) -> bool| {
+            let style = SettingsStyle::default().with_reciprocal_edges(reciprocal);
+            shapes(&ctx, &g, &style, &meta)
+                .iter()
+                .filter(|s| f(s))
+                .count()
+        };
+        let is_line = |s: &Shape| matches!(s, Shape::Path(p) if !p.closed);
+        let is_curve = |s: &Shape| matches!(s, Shape::CubicBezier(_));
+        let is_tip = |s: &Shape| matches!(s, Shape::Path(p) if p.closed);
+
+        assert_eq!(count(ReciprocalEdges::Overlap, is_line), 2);
+        assert_eq!(count(ReciprocalEdges::Curved, is_curve), 2);
+        assert_eq!(count(ReciprocalEdges::Combined, is_line), 1);
+        assert_eq!(count(ReciprocalEdges::Combined, is_tip), 2);
+    }
+}
@@ -0,0 +1,97 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use egui::{ecolor::Hsva, Color32, Pos2};
+
+/// Computes the convex hull of the points with the monotone chain algorithm.
+///
+/// Returns hull vertices in counter-clockwise order without collinear points.
+pub(crate) fn convex_hull(mut points: Vec<Pos2>) -> Vec<Pos2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // lower hull goes left to right and upper hull right to left
+    let mut lower = chain(points.iter());
+    let mut upper = chain(points.iter().rev());
+
+    // last point of each chain is the first point of the other one
+    lower.pop();
+    upper.pop();
+    lower.append(&mut upper);
+
+    lower
+}
+
+fn chain<'a>(points: impl Iterator<Item = &'a Pos2>) -> Vec<Pos2> {
+    let cross = |o: Pos2, a: Pos2, b: Pos2| (a - o).x * (b - o).y - (a - o).y * (b - o).x;
+
+    let mut res: Vec<Pos2> = vec![];
+    for p in points {
+        while res.len() >= 2 && cross(res[res.len() - 2], res[res.len() - 1], *p) <= 0. {
+            res.pop();
+        }
+        res.push(*p);
+    }
+
+    res
+}
+
+/// Picks a stable translucent color for the group from the hash of its name.
+pub(crate) fn group_color(group: &str) -> Color32 {
+    let mut hasher = DefaultHasher::new();
+    group.hash(&mut hasher);
+    let hue = (hasher.finish() % 360) as f32 / 360.;
+
+    Hsva::new(hue, 0.6, 0.8, 0.15).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull_square() {
+        let points = vec![
+            Pos2::new(0., 0.),
+            Pos2::new(1., 1.),
+            Pos2::new(2., 0.),
+            Pos2::new(0., 2.),
+            Pos2::new(2., 2.),
+            Pos2::new(1., 0.),
+        ];
+
+        assert_eq!(
+            convex_hull(points),
+            vec![
+                Pos2::new(0., 0.),
+                Pos2::new(2., 0.),
+                Pos2::new(2., 2.),
+                Pos2::new(0., 2.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_degenerate() {
+        assert_eq!(
+            convex_hull(vec![Pos2::new(1., 1.), Pos2::new(1., 1.)]),
+            vec![Pos2::new(1., 1.)]
+        );
+        assert_eq!(
+            convex_hull(vec![
+                Pos2::new(0., 0.),
+                Pos2::new(1., 1.),
+                Pos2::new(2., 2.)
+            ]),
+            vec![Pos2::new(0., 0.), Pos2::new(2., 2.)]
+        );
+    }
+
+    #[test]
+    fn test_group_color_stable() {
+        assert_eq!(group_color("a"), group_color("a"));
+        assert!(group_color("a").a() < 255);
+    }
+}
@@ -1,8 +1,17 @@
+mod bundling;
 mod displays;
 mod displays_default;
 mod drawer;
+mod hull;
+mod palette;
+mod scale_bar;
 
 pub use displays::{DisplayEdge, DisplayNode};
 pub use displays_default::DefaultEdgeShape;
 pub use displays_default::DefaultNodeShape;
 pub use drawer::{DrawContext, Drawer};
+
+pub(crate) use bundling::bundle;
+pub(crate) use palette::{component_color, normalize};
+pub use palette::{Colormap, Palette};
+pub(crate) use scale_bar::draw_scale_bar;
@@ -0,0 +1,262 @@
+use std::hash::{Hash, Hasher};
+
+use egui::{ecolor::Hsva, Color32};
+
+/// Golden ratio conjugate, stepping hue by it keeps consecutive colors far apart on the color wheel.
+const HUE_STEP: f32 = 0.618_034;
+
+/// Picks a distinct color for the component with the given ordinal number.
+pub(crate) fn component_color(ordinal: usize) -> Color32 {
+    let hue = (ordinal as f32 * HUE_STEP).fract();
+
+    Hsva::new(hue, 0.6, 0.8, 1.).into()
+}
+
+/// Maps categories to colors from a fixed list, so the same category always gets the same color
+/// across frames and sessions.
+///
+/// The default palette is the Tableau 10 palette.
+///
+/// # Example
+/// ```
+/// use egui::Color32;
+/// use egui_graphs::Palette;
+///
+/// let palette = Palette::new(vec![Color32::RED, Color32::GREEN, Color32::BLUE]);
+///
+/// assert_eq!(palette.color(&"cat"), palette.color(&"cat"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    colors: Vec<Color32>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(vec![
+            Color32::from_rgb(0x4e, 0x79, 0xa7),
+            Color32::from_rgb(0xf2, 0x8e, 0x2b),
+            Color32::from_rgb(0xe1, 0x57, 0x59),
+            Color32::from_rgb(0x76, 0xb7, 0xb2),
+            Color32::from_rgb(0x59, 0xa1, 0x4f),
+            Color32::from_rgb(0xed, 0xc9, 0x48),
+            Color32::from_rgb(0xb0, 0x7a, 0xa1),
+            Color32::from_rgb(0xff, 0x9d, 0xa7),
+            Color32::from_rgb(0x9c, 0x75, 0x5f),
+            Color32::from_rgb(0xba, 0xb0, 0xac),
+        ])
+    }
+}
+
+impl Palette {
+    /// Creates palette with custom colors.
+    ///
+    /// # Panics
+    /// Panics if `colors` is empty.
+    pub fn new(colors: Vec<Color32>) -> Self {
+        assert!(
+            !colors.is_empty(),
+            "palette should contain at least one color"
+        );
+        Self { colors }
+    }
+
+    /// Returns the color of the category.
+    ///
+    /// Categories are hashed with a fixed hash function instead of [`std::collections::hash_map::DefaultHasher`],
+    /// so colors do not change between program runs and Rust versions.
+    pub fn color<K: Hash + ?Sized>(&self, category: &K) -> Color32 {
+        let mut hasher = Fnv1a::default();
+        category.hash(&mut hasher);
+
+        self.colors[(hasher.finish() % self.colors.len() as u64) as usize]
+    }
+}
+
+/// Maps scalar values in range `[0, 1]` to colors interpolating between evenly spaced stops.
+///
+/// # Example
+/// ```
+/// use egui::Color32;
+/// use egui_graphs::Colormap;
+///
+/// let colormap = Colormap::new(vec![Color32::BLACK, Color32::WHITE]);
+///
+/// assert_eq!(colormap.color(0.), Color32::BLACK);
+/// assert_eq!(colormap.color(1.), Color32::WHITE);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Colormap {
+    stops: Vec<Color32>,
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Self::viridis()
+    }
+}
+
+impl Colormap {
+    /// Creates colormap with custom stops, the first one is used for `0` and the last one for `1`.
+    ///
+    /// # Panics
+    /// Panics if `stops` is empty.
+    pub fn new(stops: Vec<Color32>) -> Self {
+        assert!(
+            !stops.is_empty(),
+            "colormap should contain at least one color"
+        );
+        Self { stops }
+    }
+
+    /// Perceptually uniform colormap from dark purple to yellow.
+    pub fn viridis() -> Self {
+        Self::new(vec![
+            Color32::from_rgb(0x44, 0x01, 0x54),
+            Color32::from_rgb(0x3b, 0x52, 0x8b),
+            Color32::from_rgb(0x21, 0x91, 0x8c),
+            Color32::from_rgb(0x5e, 0xc9, 0x62),
+            Color32::from_rgb(0xfd, 0xe7, 0x25),
+        ])
+    }
+
+    /// Perceptually uniform colormap from black through red to light yellow.
+    pub fn inferno() -> Self {
+        Self::new(vec![
+            Color32::from_rgb(0x00, 0x00, 0x04),
+            Color32::from_rgb(0x42, 0x0a, 0x68),
+            Color32::from_rgb(0x93, 0x26, 0x67),
+            Color32::from_rgb(0xdd, 0x51, 0x3a),
+            Color32::from_rgb(0xfc, 0xa5, 0x0a),
+            Color32::from_rgb(0xfc, 0xff, 0xa4),
+        ])
+    }
+
+    /// Diverging colormap from blue through light gray to red.
+    pub fn coolwarm() -> Self {
+        Self::new(vec![
+            Color32::from_rgb(0x3b, 0x4c, 0xc0),
+            Color32::from_rgb(0xdd, 0xdd, 0xdd),
+            Color32::from_rgb(0xb4, 0x04, 0x26),
+        ])
+    }
+
+    /// Returns the color of the value, values outside of `[0, 1]` are clamped and `NaN` is treated as `0`.
+    pub fn color(&self, value: f32) -> Color32 {
+        let value = if value.is_nan() {
+            0.
+        } else {
+            value.clamp(0., 1.)
+        };
+
+        let pos = value * (self.stops.len() - 1) as f32;
+        let idx = (pos.floor() as usize).min(self.stops.len() - 1);
+        let Some(next) = self.stops.get(idx + 1) else {
+            return self.stops[idx];
+        };
+
+        self.stops[idx].lerp_to_gamma(*next, pos - idx as f32)
+    }
+}
+
+/// Normalizes values to `[0, 1]` over their range. Non-finite values are mapped to `0`, equal values to `0.5`.
+pub(crate) fn normalize(values: &[f32]) -> Vec<f32> {
+    let (min, max) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f32::MAX, f32::MIN), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        });
+
+    values
+        .iter()
+        .map(|v| {
+            if !v.is_finite() {
+                0.
+            } else if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            }
+        })
+        .collect()
+}
+
+/// FNV-1a hash function, stable and good enough for spreading categories over the palette.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_color() {
+        let palette = Palette::default();
+
+        assert_eq!(palette.color("a"), palette.color("a"));
+        assert_eq!(palette.color(&1_u32), palette.color(&1_u32));
+
+        let single = Palette::new(vec![Color32::RED]);
+        assert_eq!(single.color("a"), Color32::RED);
+        assert_eq!(single.color(&42), Color32::RED);
+    }
+
+    #[test]
+    fn test_palette_spread() {
+        let palette = Palette::default();
+        let colors = (0..100)
+            .map(|i| palette.color(&i))
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(colors.len(), 10);
+    }
+
+    #[test]
+    fn test_colormap_color() {
+        let colormap = Colormap::new(vec![Color32::BLACK, Color32::WHITE, Color32::RED]);
+
+        assert_eq!(colormap.color(0.), Color32::BLACK);
+        assert_eq!(colormap.color(0.5), Color32::WHITE);
+        assert_eq!(colormap.color(1.), Color32::RED);
+        assert_eq!(colormap.color(2.), Color32::RED);
+        assert_eq!(colormap.color(f32::NAN), Color32::BLACK);
+        assert_eq!(
+            colormap.color(0.25),
+            Color32::BLACK.lerp_to_gamma(Color32::WHITE, 0.5)
+        );
+
+        assert_eq!(Colormap::new(vec![Color32::RED]).color(0.7), Color32::RED);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(&[2., 4., 3., f32::NAN]), vec![0., 1., 0.5, 0.]);
+        assert_eq!(normalize(&[1., 1.]), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_component_color() {
+        assert_eq!(component_color(3), component_color(3));
+        assert_ne!(component_color(0), component_color(1));
+        assert_ne!(component_color(1), component_color(2));
+    }
+}
@@ -0,0 +1,73 @@
+use egui::{Align2, FontId, Painter, Pos2, Rect, Stroke, Vec2, Visuals};
+
+/// Maximal length of the scale bar in screen points.
+const MAX_LENGTH: f32 = 100.;
+/// Distance in screen points between the scale bar and the corner of the widget.
+const MARGIN: f32 = 10.;
+/// Height in screen points of the ticks at the ends of the scale bar.
+const TICK: f32 = 4.;
+
+/// Draws a scale bar in the bottom left corner of `rect` showing the length of the bar in canvas units.
+pub(crate) fn draw_scale_bar(painter: &Painter, rect: Rect, zoom: f32, visuals: &Visuals) {
+    let Some(units) = nice_length(MAX_LENGTH / zoom) else {
+        return;
+    };
+    let length = units * zoom;
+
+    let stroke = Stroke::new(1., visuals.text_color());
+    let start = Pos2::new(rect.left() + MARGIN, rect.bottom() - MARGIN);
+    let end = start + Vec2::new(length, 0.);
+    painter.line_segment([start, end], stroke);
+    for p in [start, end] {
+        painter.line_segment([p, p - Vec2::new(0., TICK)], stroke);
+    }
+
+    painter.text(
+        start + Vec2::new(length / 2., -TICK),
+        Align2::CENTER_BOTTOM,
+        format!("{units:.*}", decimals(units)),
+        FontId::monospace(10.),
+        visuals.text_color(),
+    );
+}
+
+/// Returns the largest number of the form 1, 2 or 5 multiplied by a power of ten not exceeding `max`.
+fn nice_length(max: f32) -> Option<f32> {
+    if !max.is_finite() || max <= 0. {
+        return None;
+    }
+
+    let magnitude = 10_f32.powf(max.log10().floor());
+    [5., 2., 1.]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|l| *l <= max)
+}
+
+/// Number of decimal places needed to show the length without float noise.
+fn decimals(length: f32) -> usize {
+    (-length.log10().floor()).max(0.) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_length() {
+        assert_eq!(nice_length(100.), Some(100.));
+        assert_eq!(nice_length(99.), Some(50.));
+        assert_eq!(nice_length(30.), Some(20.));
+        assert_eq!(nice_length(1.5), Some(1.));
+        assert!((nice_length(0.07).unwrap() - 0.05).abs() < 1e-6);
+        assert_eq!(nice_length(0.), None);
+        assert_eq!(nice_length(f32::INFINITY), None);
+    }
+
+    #[test]
+    fn test_label() {
+        let label = |l: f32| format!("{l:.*}", decimals(l));
+        assert_eq!(label(nice_length(0.07).unwrap()), "0.05");
+        assert_eq!(label(200.), "200");
+    }
+}
@@ -1,12 +1,13 @@
 use std::marker::PhantomData;
 
+use egui::Pos2;
 use petgraph::{
     stable_graph::{DefaultIx, EdgeIndex, IndexType},
     Directed, EdgeType,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{DefaultEdgeShape, DefaultNodeShape, DisplayEdge, DisplayNode};
+use crate::{DefaultEdgeShape, DefaultNodeShape, DisplayEdge, DisplayNode, ReciprocalEdges};
 
 /// Stores properties of an [Edge]
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -15,6 +16,55 @@ pub struct EdgeProps<E: Clone> {
     pub order: usize,
     pub selected: bool,
     pub label: String,
+    #[serde(default = "weight_default")]
+    pub weight: f32,
+    #[serde(default)]
+    pub curvature: f32,
+
+    #[serde(skip)]
+    bundle: Option<[Pos2; 2]>,
+    #[serde(skip)]
+    hovered: bool,
+    #[serde(skip)]
+    reciprocal: ReciprocalEdges,
+    #[serde(skip)]
+    mutual: bool,
+}
+
+/// Edges serialized before weights were introduced keep the neutral weight.
+fn weight_default() -> f32 {
+    1.
+}
+
+impl<E: Clone> EdgeProps<E> {
+    /// Control points in canvas coordinates of the curve the edge follows when it is bundled with similar edges.
+    pub fn bundle(&self) -> Option<[Pos2; 2]> {
+        self.bundle
+    }
+
+    /// Whether the pointer is over the edge.
+    pub fn hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// How the edge is drawn together with the edge going in the opposite direction between the same nodes.
+    /// [`ReciprocalEdges::Overlap`] if there is no such edge.
+    pub fn reciprocal(&self) -> ReciprocalEdges {
+        self.reciprocal
+    }
+
+    pub(crate) fn set_reciprocal(&mut self, reciprocal: ReciprocalEdges) {
+        self.reciprocal = reciprocal;
+    }
+
+    /// Whether the edge of a directed graph has an edge going in the opposite direction between the same nodes.
+    pub fn mutual(&self) -> bool {
+        self.mutual
+    }
+
+    pub(crate) fn set_mutual(&mut self, mutual: bool) {
+        self.mutual = mutual;
+    }
 }
 
 /// Stores properties of an edge that can be changed. Used to apply changes to the graph.
@@ -51,6 +101,12 @@ impl<
             order: usize::default(),
             selected: bool::default(),
             label: String::default(),
+            weight: 1.,
+            curvature: f32::default(),
+            bundle: Option::default(),
+            hovered: bool::default(),
+            reciprocal: ReciprocalEdges::default(),
+            mutual: bool::default(),
         };
 
         let display = D::from(props.clone());
@@ -92,6 +148,30 @@ impl<
         self.props.order = order;
     }
 
+    pub fn weight(&self) -> f32 {
+        self.props.weight
+    }
+
+    /// Sets how strongly the edge pulls its endpoints together in the force-directed layout.
+    /// Weight `1.0` is the neutral value, non-positive weights are treated as a tiny positive one.
+    pub fn set_weight(&mut self, weight: f32) {
+        self.props.weight = weight;
+    }
+
+    pub fn curvature(&self) -> f32 {
+        self.props.curvature
+    }
+
+    /// Sets how far in canvas units the middle of the edge bows away from the straight line between its nodes.
+    /// The sign selects the side, zero keeps the edge straight. Added to the offset of parallel edges.
+    pub fn set_curvature(&mut self, curvature: f32) {
+        self.props.curvature = curvature;
+    }
+
+    pub(crate) fn set_bundle(&mut self, bundle: Option<[Pos2; 2]>) {
+        self.props.bundle = bundle;
+    }
+
     pub fn payload(&self) -> &E {
         &self.props.payload
     }
@@ -108,6 +188,14 @@ impl<
         self.props.selected
     }
 
+    pub fn hovered(&self) -> bool {
+        self.props.hovered()
+    }
+
+    pub(crate) fn set_hovered(&mut self, hovered: bool) {
+        self.props.hovered = hovered;
+    }
+
     pub fn set_label(&mut self, label: String) {
         self.props.label = label;
     }
@@ -121,3 +209,20 @@ impl<
         self.props.label.clone()
     }
 }
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_props_without_new_fields() {
+        let mut value = serde_json::to_value(Edge::<(), ()>::new(()).props()).unwrap();
+        for field in ["weight", "curvature"] {
+            value.as_object_mut().unwrap().remove(field).unwrap();
+        }
+
+        let props: EdgeProps<()> = serde_json::from_value(value).unwrap();
+        assert_eq!(props.weight, 1.);
+        assert_eq!(props.curvature, 0.);
+    }
+}
@@ -2,4 +2,4 @@ mod edge;
 mod node;
 
 pub use edge::{Edge, EdgeProps};
-pub use node::{Node, NodeProps};
+pub use node::{Badge, BadgePosition, Node, NodeProps};
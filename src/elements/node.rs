@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
-use egui::{Color32, Pos2};
+use egui::{Color32, Pos2, TextureId};
 use petgraph::{
     stable_graph::{DefaultIx, IndexType, NodeIndex},
     Directed, EdgeType,
@@ -22,8 +22,88 @@ where
     pub dragged: bool,
 
     color: Option<Color32>,
+    #[serde(skip)]
+    color_computed: Option<Color32>,
+    #[serde(skip)]
+    alpha_computed: Option<f32>,
+    #[serde(skip)]
+    size_scale_computed: Option<f32>,
     location: Pos2,
     location_user: Option<Pos2>,
+    #[serde(default = "opacity_default")]
+    opacity: f32,
+    fade: Option<Fade>,
+    group: Option<String>,
+    #[serde(default)]
+    collapsed: bool,
+    #[serde(default = "interactable_default")]
+    interactable: bool,
+    #[serde(default)]
+    dirty: bool,
+    #[serde(default)]
+    z: i32,
+    #[serde(skip)]
+    texture: Option<TextureId>,
+    badge: Option<Badge>,
+    #[serde(skip)]
+    hovered: bool,
+}
+
+/// Nodes serialized before opacity was introduced stay visible.
+fn opacity_default() -> f32 {
+    1.
+}
+
+/// Nodes serialized before interaction could be disabled per node stay interactable.
+fn interactable_default() -> bool {
+    true
+}
+
+/// Small label drawn by [`DefaultNodeShape`] in a corner of the node, e.g. a number of aggregated items.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Badge {
+    pub text: String,
+    pub position: BadgePosition,
+    /// Fill color of the badge circle, text is drawn in white or black whichever contrasts more.
+    pub color: Color32,
+}
+
+impl Badge {
+    /// Creates a red badge in the top right corner of the node.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            position: BadgePosition::default(),
+            color: Color32::RED,
+        }
+    }
+
+    pub fn with_position(mut self, position: BadgePosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// Corner of the node a [`Badge`] is drawn at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BadgePosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Opacity animation of a node driven by [`crate::GraphView`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct Fade {
+    target: f32,
+    remove: bool,
 }
 
 impl<N> NodeProps<N>
@@ -34,8 +114,58 @@ where
         self.location_user.unwrap_or(self.location)
     }
 
+    /// Color set by the user or computed by the widget from style settings.
     pub fn color(&self) -> Option<Color32> {
-        self.color
+        self.color.or(self.color_computed)
+    }
+
+    /// Opacity of the fade animation multiplied by the alpha computed by the widget, e.g. for filtered out nodes.
+    pub fn opacity(&self) -> f32 {
+        self.opacity * self.alpha_computed.unwrap_or(1.)
+    }
+
+    /// Multiplier of node sizes in canvas units computed by the widget, `1 / zoom` when nodes keep constant
+    /// screen size, see [`crate::SettingsStyle::with_fixed_node_screen_size`].
+    pub fn size_scale(&self) -> f32 {
+        self.size_scale_computed.unwrap_or(1.)
+    }
+
+    /// Multiplies opacity by the factor without affecting fades, used to draw nodes dimmed.
+    pub(crate) fn dim(&mut self, factor: f32) {
+        self.opacity *= factor;
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    pub fn collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    pub fn interactable(&self) -> bool {
+        self.interactable
+    }
+
+    pub fn dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+
+    pub fn texture(&self) -> Option<TextureId> {
+        self.texture
+    }
+
+    pub fn badge(&self) -> Option<&Badge> {
+        self.badge.as_ref()
+    }
+
+    /// Whether the pointer is over the node.
+    pub fn hovered(&self) -> bool {
+        self.hovered
     }
 }
 
@@ -103,10 +233,23 @@ where
             payload,
             location: Pos2::default(),
             color: Option::default(),
+            color_computed: Option::default(),
+            alpha_computed: Option::default(),
+            size_scale_computed: Option::default(),
             location_user: Option::default(),
             label: String::default(),
             selected: bool::default(),
             dragged: bool::default(),
+            opacity: 1.,
+            fade: Option::default(),
+            group: Option::default(),
+            collapsed: bool::default(),
+            interactable: true,
+            dirty: bool::default(),
+            z: i32::default(),
+            texture: Option::default(),
+            badge: Option::default(),
+            hovered: bool::default(),
         };
 
         Node::new_with_props(props)
@@ -161,14 +304,31 @@ where
         self.props.color = Some(color);
     }
 
+    pub(crate) fn set_color_computed(&mut self, color: Option<Color32>) {
+        self.props.color_computed = color;
+    }
+
+    pub(crate) fn set_alpha_computed(&mut self, alpha: Option<f32>) {
+        self.props.alpha_computed = alpha.map(|a| a.clamp(0., 1.));
+    }
+
     pub fn location(&self) -> Pos2 {
         self.props.location()
     }
 
+    pub(crate) fn set_size_scale_computed(&mut self, scale: Option<f32>) {
+        self.props.size_scale_computed = scale;
+    }
+
     pub fn set_location(&mut self, loc: Pos2) {
         self.props.location_user = Some(loc);
     }
 
+    /// Location set by the user, layouts do not move nodes which have it.
+    pub(crate) fn location_user(&self) -> Option<Pos2> {
+        self.props.location_user
+    }
+
     // TODO: why crate? how to use by external layoyuts?? do we need this func???
     pub(crate) fn set_layout_location(&mut self, loc: Pos2) {
         self.props.location = loc;
@@ -190,6 +350,151 @@ where
         self.props.dragged = dragged;
     }
 
+    pub fn hovered(&self) -> bool {
+        self.props.hovered()
+    }
+
+    pub(crate) fn set_hovered(&mut self, hovered: bool) {
+        self.props.hovered = hovered;
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.props.opacity()
+    }
+
+    pub(crate) fn dim(&mut self, factor: f32) {
+        self.props.dim(factor);
+    }
+
+    /// Sets node opacity in range `[0, 1]` stopping any running fade.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.props.opacity = opacity.clamp(0., 1.);
+        self.props.fade = None;
+    }
+
+    /// Makes the node fully transparent and fades it in.
+    ///
+    /// The fade is animated by [`crate::GraphView`] with the duration from [`crate::SettingsStyle`].
+    pub fn fade_in(&mut self) {
+        self.props.opacity = 0.;
+        self.props.fade = Some(Fade {
+            target: 1.,
+            remove: false,
+        });
+    }
+
+    /// Fades the node out keeping it in the graph.
+    ///
+    /// The fade is animated by [`crate::GraphView`] with the duration from [`crate::SettingsStyle`].
+    pub fn fade_out(&mut self) {
+        self.props.fade = Some(Fade {
+            target: 0.,
+            remove: false,
+        });
+    }
+
+    /// Whether the node opacity is being animated.
+    pub fn fading(&self) -> bool {
+        self.props.fade.is_some()
+    }
+
+    pub(crate) fn fade_out_and_remove(&mut self) {
+        self.props.fade = Some(Fade {
+            target: 0.,
+            remove: true,
+        });
+    }
+
+    /// Moves opacity towards the fade target by `step`.
+    /// Returns true if the fade is finished and the node should be removed.
+    pub(crate) fn step_fade(&mut self, step: f32) -> bool {
+        let Some(fade) = self.props.fade else {
+            return false;
+        };
+
+        let diff = fade.target - self.props.opacity;
+        if diff.abs() > step {
+            self.props.opacity += step.copysign(diff);
+            return false;
+        }
+
+        self.props.opacity = fade.target;
+        self.props.fade = None;
+        fade.remove
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        self.props.group()
+    }
+
+    /// Assigns the node to a named group. Nodes of the same group are drawn over a translucent
+    /// background of their convex hull.
+    pub fn set_group(&mut self, group: Option<String>) {
+        self.props.group = group;
+    }
+
+    pub fn collapsed(&self) -> bool {
+        self.props.collapsed()
+    }
+
+    /// Marks the node as collapsed. Descendants of collapsed nodes are hidden by [`crate::GraphView`],
+    /// prefer [`crate::Graph::collapse`] and [`crate::Graph::expand`] which also update hidden nodes immediately.
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.props.collapsed = collapsed;
+    }
+
+    pub fn interactable(&self) -> bool {
+        self.props.interactable()
+    }
+
+    /// Allows or forbids clicking, selecting and dragging the node in [`crate::GraphView`] on top of
+    /// the global interaction settings. Pointer interactions pass through non-interactable nodes to
+    /// nodes, edges or empty space below them. Such nodes are still drawn and can be connected with new edges.
+    pub fn set_interactable(&mut self, interactable: bool) {
+        self.props.interactable = interactable;
+    }
+
+    pub fn dirty(&self) -> bool {
+        self.props.dirty()
+    }
+
+    /// Marks the node as not yet placed by the layout. Nodes added with [`crate::Graph`] helpers are dirty.
+    /// Incremental force-directed layout moves dirty nodes freely and clears the flag once they settle.
+    pub fn set_dirty(&mut self, dirty: bool) {
+        self.props.dirty = dirty;
+    }
+
+    pub fn z(&self) -> i32 {
+        self.props.z()
+    }
+
+    /// Sets draw priority of the node. Nodes with higher values are drawn on top of nodes with lower ones,
+    /// nodes with equal values are drawn in insertion order. Selected and dragged nodes are always drawn on top.
+    pub fn set_z(&mut self, z: i32) {
+        self.props.z = z;
+    }
+
+    pub fn texture(&self) -> Option<TextureId> {
+        self.props.texture()
+    }
+
+    /// Sets image drawn inside the node, e.g. an avatar. Textures are not serialized.
+    ///
+    /// Load the image with [`egui::Context::load_texture`] and keep the returned handle alive
+    /// while the node shows it.
+    pub fn set_texture(&mut self, texture: Option<TextureId>) {
+        self.props.texture = texture;
+    }
+
+    pub fn badge(&self) -> Option<&Badge> {
+        self.props.badge()
+    }
+
+    /// Sets badge drawn in a corner of the node, `None` removes it.
+    pub fn set_badge(&mut self, badge: Option<Badge>) {
+        self.props.badge = badge;
+    }
+
     pub fn label(&self) -> String {
         self.props.label.clone()
     }
@@ -203,3 +508,76 @@ where
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_deserialize_props_without_new_fields() {
+        let mut value = serde_json::to_value(Node::<(), ()>::new(()).props()).unwrap();
+        for field in ["opacity", "collapsed", "interactable", "dirty", "z"] {
+            value.as_object_mut().unwrap().remove(field).unwrap();
+        }
+
+        let props: NodeProps<()> = serde_json::from_value(value).unwrap();
+        assert_eq!(props.opacity(), 1.);
+        assert!(!props.collapsed());
+        assert!(props.interactable());
+        assert!(!props.dirty());
+        assert_eq!(props.z(), 0);
+    }
+
+    #[test]
+    fn test_step_fade_in() {
+        let mut n = Node::<(), ()>::new(());
+        n.fade_in();
+        assert_eq!(n.opacity(), 0.);
+
+        assert!(!n.step_fade(0.25));
+        assert_eq!(n.opacity(), 0.25);
+        assert!(n.fading());
+
+        assert!(!n.step_fade(1.));
+        assert_eq!(n.opacity(), 1.);
+        assert!(!n.fading());
+    }
+
+    #[test]
+    fn test_alpha_computed() {
+        let mut n = Node::<(), ()>::new(());
+        n.set_opacity(0.5);
+        n.set_alpha_computed(Some(0.5));
+        assert_eq!(n.opacity(), 0.25);
+
+        n.set_alpha_computed(Some(2.));
+        assert_eq!(n.opacity(), 0.5);
+
+        n.set_alpha_computed(None);
+        assert_eq!(n.opacity(), 0.5);
+    }
+
+    #[test]
+    fn test_step_fade_out_and_remove() {
+        let mut n = Node::<(), ()>::new(());
+        n.fade_out_and_remove();
+
+        assert!(!n.step_fade(0.5));
+        assert_eq!(n.opacity(), 0.5);
+        assert!(n.step_fade(0.5));
+        assert_eq!(n.opacity(), 0.);
+    }
+
+    #[test]
+    fn test_set_opacity_stops_fade() {
+        let mut n = Node::<(), ()>::new(());
+        n.fade_out();
+        n.set_opacity(2.);
+
+        assert!(!n.fading());
+        assert_eq!(n.opacity(), 1.);
+        assert!(!n.step_fade(0.5));
+        assert_eq!(n.opacity(), 1.);
+    }
+}
@@ -12,6 +12,14 @@ pub struct PayloadZoom {
     pub new_zoom: f32,
 }
 
+/// Camera was changed by fitting the graph to the screen rather than by the user.
+/// Emitted after pan and zoom events caused by the fit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadFitToScreen {
+    pub new_zoom: f32,
+    pub new_pan: [f32; 2],
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PayloadNodeMove {
     pub id: usize,
@@ -24,9 +32,13 @@ pub struct PayloadNodeDragStart {
     pub id: usize,
 }
 
+/// End of node dragging, `from` and `to` are node locations in canvas coordinates at the start and
+/// at the end of the gesture.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PayloadNodeDragEnd {
     pub id: usize,
+    pub from: [f32; 2],
+    pub to: [f32; 2],
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -39,6 +51,12 @@ pub struct PayloadNodeDeselect {
     pub id: usize,
 }
 
+/// Node was removed by the user together with its edges, which are reported before it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadNodeDelete {
+    pub id: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PayloadNodeClick {
     pub id: usize,
@@ -54,6 +72,11 @@ pub struct PayloadEdgeClick {
     pub id: usize,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadEdgeDoubleClick {
+    pub id: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PayloadEdgeSelect {
     pub id: usize,
@@ -64,10 +87,41 @@ pub struct PayloadEdgeDeselect {
     pub id: usize,
 }
 
+/// Edge was removed by the user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadEdgeDelete {
+    pub id: usize,
+}
+
+/// Pointer started hovering the edge.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadEdgeHover {
+    pub id: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadEdgeCreate {
+    pub source: usize,
+    pub target: usize,
+}
+
+/// Click on empty space of the canvas, `pos` is in canvas coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadCanvasClick {
+    pub pos: [f32; 2],
+}
+
+/// Double click on empty space of the canvas, `pos` is in canvas coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PayloadCanvasDoubleClick {
+    pub pos: [f32; 2],
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Event {
     Pan(PayloadPan),
     Zoom(PayloadZoom),
+    FitToScreen(PayloadFitToScreen),
     NodeMove(PayloadNodeMove),
     NodeDragStart(PayloadNodeDragStart),
     NodeDragEnd(PayloadNodeDragEnd),
@@ -75,7 +129,16 @@ pub enum Event {
     NodeDeselect(PayloadNodeDeselect),
     NodeClick(PayloadNodeClick),
     NodeDoubleClick(PayloadNodeDoubleClick),
+    NodeDelete(PayloadNodeDelete),
     EdgeClick(PayloadEdgeClick),
+    EdgeDoubleClick(PayloadEdgeDoubleClick),
     EdgeSelect(PayloadEdgeSelect),
     EdgeDeselect(PayloadEdgeDeselect),
+    EdgeHover(PayloadEdgeHover),
+    EdgeCreate(PayloadEdgeCreate),
+    EdgeDelete(PayloadEdgeDelete),
+    CanvasClick(PayloadCanvasClick),
+    CanvasDoubleClick(PayloadCanvasDoubleClick),
+    /// Sent once at the end of the first frame, after the initial layout step and fit to screen.
+    Ready,
 }
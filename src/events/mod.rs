@@ -1,7 +1,20 @@
 mod event;
 
+#[cfg(feature = "events")]
+use crossbeam::channel::Sender;
+
 pub use event::{
-    Event, PayloadEdgeClick, PayloadEdgeDeselect, PayloadEdgeSelect, PayloadNodeClick,
+    Event, PayloadCanvasClick, PayloadCanvasDoubleClick, PayloadEdgeClick, PayloadEdgeCreate,
+    PayloadEdgeDelete, PayloadEdgeDeselect, PayloadEdgeDoubleClick, PayloadEdgeHover,
+    PayloadEdgeSelect, PayloadFitToScreen, PayloadNodeClick, PayloadNodeDelete,
     PayloadNodeDeselect, PayloadNodeDoubleClick, PayloadNodeDragEnd, PayloadNodeDragStart,
     PayloadNodeMove, PayloadNodeSelect, PayloadPan, PayloadZoom,
 };
+
+#[cfg(feature = "events")]
+/// Sends the event dropping it if the receiver is disconnected.
+///
+/// The only error is a disconnected receiver, nobody is interested in events anymore.
+pub(crate) fn send_ignoring_disconnect(sender: &Sender<Event>, event: Event) {
+    let _ = sender.send(event);
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "image")]
+mod raster;
+mod svg;
+
+#[cfg(feature = "image")]
+pub use raster::to_image;
+pub use svg::to_svg;
+
+use egui::{Context, LayerId, Painter, RawInput, Rect, Shape};
+use petgraph::{stable_graph::IndexType, EdgeType};
+
+use crate::{
+    draw::{DrawContext, Drawer},
+    layouts, DisplayEdge, DisplayNode, Graph, Metadata, SettingsStyle,
+};
+
+/// Generates shapes of the graph outside of the widget running a single pass of the provided headless [`egui::Context`].
+///
+/// The graph is cloned because displays are updated with the current node and edge properties before drawing.
+/// Node displays are synced up front as edges are drawn before nodes and use their boundaries.
+pub(crate) fn shapes<N, E, Ty, Ix, Dn, De>(
+    ctx: &Context,
+    g: &Graph<N, E, Ty, Ix, Dn, De>,
+    style: &SettingsStyle,
+    meta: &Metadata,
+) -> Vec<Shape>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType + Clone,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    let mut res = vec![];
+
+    // fonts are initialized with the first pass
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        let mut g = g.clone();
+        g.g.node_weights_mut().for_each(|n| {
+            n.set_size_scale_computed(style.node_size_scale(meta.zoom));
+            let props = n.props().clone();
+            n.display_mut().update(&props);
+        });
+
+        let painter = Painter::new(ctx.clone(), LayerId::background(), Rect::EVERYTHING);
+        let draw_ctx = DrawContext {
+            ctx,
+            painter: &painter,
+            style,
+            visuals: &style.visuals(&ctx.style().visuals),
+            is_directed: g.is_directed(),
+            meta,
+        };
+
+        res = Drawer::<_, _, _, _, _, _, layouts::random::State, layouts::random::Random>::new(
+            &mut g, &draw_ctx,
+        )
+        .shapes();
+    });
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::graph;
+
+    #[test]
+    fn test_shapes_do_not_depend_on_pixels_per_point() {
+        let g = graph();
+
+        let mut meta = Metadata::default();
+        meta.zoom = 2.;
+        let style = SettingsStyle::default();
+
+        let ctx = Context::default();
+        let ctx_hidpi = Context::default();
+        ctx_hidpi.set_pixels_per_point(2.);
+
+        assert_eq!(
+            shapes(&ctx, &g, &style, &meta),
+            shapes(&ctx_hidpi, &g, &style, &meta)
+        );
+    }
+}
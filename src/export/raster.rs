@@ -0,0 +1,275 @@
+use egui::{
+    epaint::{ClippedShape, Mesh, Primitive, Vertex},
+    Color32, Context, Pos2, Rect, TextureId, Vec2,
+};
+use image::RgbaImage;
+use petgraph::{stable_graph::IndexType, EdgeType};
+
+use crate::{DisplayEdge, DisplayNode, Graph, Metadata, SettingsStyle};
+
+/// Padding around the graph relative to its size.
+const PADDING: f32 = 0.1;
+
+/// Renders the graph into an image of `size` pixels.
+///
+/// The graph is scaled and centered to fit the image, independently of the zoom and pan of the widget on the screen.
+/// Shapes are generated by the same [`DisplayNode`] and [`DisplayEdge`] implementations the widget uses and are
+/// rasterized in software from the tessellated meshes, so no GPU or window is needed.
+///
+/// The background is transparent unless [`SettingsStyle::with_background_color`] is set. Save the result with [`RgbaImage::save`] to get a png file.
+/// Labels are rendered only if egui has fonts, e.g. with its `default_fonts` feature enabled.
+///
+/// # Example
+/// ```
+/// use egui_graphs::{export::to_image, Graph, SettingsStyle};
+/// use petgraph::stable_graph::StableGraph;
+///
+/// let mut g = StableGraph::new();
+/// g.add_node(());
+///
+/// let img = to_image(&Graph::<()>::from(&g), &SettingsStyle::default(), [64, 32]);
+///
+/// assert_eq!(img.dimensions(), (64, 32));
+/// ```
+pub fn to_image<N, E, Ty, Ix, Dn, De>(
+    g: &Graph<N, E, Ty, Ix, Dn, De>,
+    style: &SettingsStyle,
+    size: [u32; 2],
+) -> RgbaImage
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType + Clone,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    let [width, height] = size;
+    let canvas = Rect::from_min_size(Pos2::ZERO, Vec2::new(width as f32, height as f32));
+
+    let mut meta = Metadata::default();
+    g.nodes_iter().for_each(|(_, n)| meta.comp_iter_bounds(n));
+    fit(&mut meta, canvas);
+
+    let ctx = Context::default();
+    let shapes = super::shapes(&ctx, g, style, &meta)
+        .into_iter()
+        .map(|shape| ClippedShape {
+            clip_rect: canvas,
+            shape,
+        })
+        .collect();
+    let primitives = ctx.tessellate(shapes, 1.);
+    let font = ctx.fonts(|f| {
+        let img = f.image();
+        (img.size, img.srgba_pixels(None).collect::<Vec<_>>())
+    });
+
+    let mut canvas = Canvas::new(width as usize, height as usize);
+    if let Some(color) = style.background_color {
+        canvas.pixels.fill(color);
+    }
+    for p in &primitives {
+        if let Primitive::Mesh(mesh) = &p.primitive {
+            canvas.mesh(mesh, &font);
+        }
+    }
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        image::Rgba(canvas.pixel(x as usize, y as usize).to_srgba_unmultiplied())
+    })
+}
+
+/// Sets zoom and pan of `meta` so the graph bounds fit into the `canvas`.
+fn fit(meta: &mut Metadata, canvas: Rect) {
+    let bounds = meta.graph_bounds();
+    let mut diag = bounds.max - bounds.min;
+
+    // if the graph is empty or consists from one node, use a default size
+    if !diag.is_finite() || diag.x <= 0. || diag.y <= 0. {
+        diag = Vec2::new(1., 100.);
+    }
+
+    let graph_size = diag * (1. + PADDING);
+    let zoom = (canvas.width() / graph_size.x).min(canvas.height() / graph_size.y);
+    let center = if bounds.is_finite() {
+        bounds.center().to_vec2()
+    } else {
+        Vec2::ZERO
+    };
+
+    meta.zoom = zoom;
+    meta.pan = canvas.center().to_vec2() - center * zoom;
+}
+
+/// Premultiplied pixel buffer with alpha blending of triangle meshes.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color32>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color32::TRANSPARENT; width * height],
+        }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Color32 {
+        self.pixels[y * self.width + x]
+    }
+
+    fn mesh(&mut self, mesh: &Mesh, font: &([usize; 2], Vec<Color32>)) {
+        // font atlas is the only texture known outside of the renderer
+        let texture = (mesh.texture_id == TextureId::default()).then_some(font);
+
+        mesh.indices.chunks_exact(3).for_each(|idxs| {
+            let triangle = [
+                mesh.vertices[idxs[0] as usize],
+                mesh.vertices[idxs[1] as usize],
+                mesh.vertices[idxs[2] as usize],
+            ];
+            self.triangle(&triangle, texture);
+        });
+    }
+
+    fn triangle(&mut self, t: &[Vertex; 3], texture: Option<&([usize; 2], Vec<Color32>)>) {
+        let [v0, v1, v2] = t;
+        let area = edge(v0.pos, v1.pos, v2.pos);
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let min = v0.pos.min(v1.pos).min(v2.pos);
+        let max = v0.pos.max(v1.pos).max(v2.pos);
+        let (x_min, y_min) = (
+            min.x.floor().max(0.) as usize,
+            min.y.floor().max(0.) as usize,
+        );
+        let x_max = (max.x.ceil().max(0.) as usize).min(self.width);
+        let y_max = (max.y.ceil().max(0.) as usize).min(self.height);
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                // sample in the pixel center
+                let pos = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(v1.pos, v2.pos, pos) / area;
+                let w1 = edge(v2.pos, v0.pos, pos) / area;
+                let w2 = 1. - w0 - w1;
+                if w0 < 0. || w1 < 0. || w2 < 0. {
+                    continue;
+                }
+
+                let mut color = interpolate([v0.color, v1.color, v2.color], [w0, w1, w2]);
+                if let Some(([tex_width, tex_height], texels)) = texture {
+                    let uv = v0.uv.to_vec2() * w0 + v1.uv.to_vec2() * w1 + v2.uv.to_vec2() * w2;
+                    let tx = ((uv.x * *tex_width as f32) as usize).min(tex_width - 1);
+                    let ty = ((uv.y * *tex_height as f32) as usize).min(tex_height - 1);
+                    color = modulate(color, texels[ty * tex_width + tx]);
+                }
+
+                let dst = &mut self.pixels[y * self.width + x];
+                *dst = blend(*dst, color);
+            }
+        }
+    }
+}
+
+/// Signed doubled area of the triangle `abc`.
+fn edge(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn interpolate(colors: [Color32; 3], weights: [f32; 3]) -> Color32 {
+    let channel = |i: usize| {
+        let v = colors
+            .iter()
+            .zip(weights)
+            .map(|(c, w)| f32::from(c.to_array()[i]) * w)
+            .sum::<f32>();
+        v.round().clamp(0., 255.) as u8
+    };
+
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+/// Multiplies colors component-wise.
+fn modulate(a: Color32, b: Color32) -> Color32 {
+    let channel = |x: u8, y: u8| ((u16::from(x) * u16::from(y) + 127) / 255) as u8;
+
+    Color32::from_rgba_premultiplied(
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+        channel(a.a(), b.a()),
+    )
+}
+
+/// Blends premultiplied `src` over `dst`.
+fn blend(dst: Color32, src: Color32) -> Color32 {
+    let inv = 255 - u16::from(src.a());
+    let channel = |s: u8, d: u8| (u16::from(s) + (u16::from(d) * inv + 127) / 255).min(255) as u8;
+
+    Color32::from_rgba_premultiplied(
+        channel(src.r(), dst.r()),
+        channel(src.g(), dst.g()),
+        channel(src.b(), dst.b()),
+        channel(src.a(), dst.a()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_opaque() {
+        assert_eq!(blend(Color32::RED, Color32::BLUE), Color32::BLUE);
+    }
+
+    #[test]
+    fn test_blend_transparent() {
+        assert_eq!(blend(Color32::RED, Color32::TRANSPARENT), Color32::RED);
+    }
+
+    #[test]
+    fn test_modulate() {
+        assert_eq!(modulate(Color32::RED, Color32::WHITE), Color32::RED);
+        assert_eq!(
+            modulate(Color32::RED, Color32::TRANSPARENT),
+            Color32::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn test_edge_orientation() {
+        let (a, b, c) = (Pos2::ZERO, Pos2::new(1., 0.), Pos2::new(0., 1.));
+        assert_eq!(edge(a, b, c), 1.);
+        assert_eq!(edge(a, c, b), -1.);
+    }
+
+    #[test]
+    fn test_to_image_draws_node() {
+        let mut g = crate::Graph::<()>::new(petgraph::stable_graph::StableGraph::default());
+        g.add_node_with_location((), Pos2::new(0., 0.));
+        g.add_node_with_location((), Pos2::new(100., 100.));
+
+        let img = to_image(&g, &SettingsStyle::default(), [32, 32]);
+
+        assert!(img.pixels().any(|p| p.0[3] > 0));
+        assert_eq!(img.get_pixel(0, 31).0[3], 0);
+    }
+
+    #[test]
+    fn test_to_image_background() {
+        let g = crate::Graph::<()>::new(petgraph::stable_graph::StableGraph::default());
+        let style = SettingsStyle::default().with_background_color(Some(Color32::RED));
+
+        let img = to_image(&g, &style, [8, 8]);
+
+        assert!(img.pixels().all(|p| p.0 == [255, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,267 @@
+use egui::{
+    epaint::{ColorMode, TextShape},
+    Color32, Context, Pos2, Rect, Shape, Stroke,
+};
+use petgraph::{stable_graph::IndexType, EdgeType};
+
+use crate::{DisplayEdge, DisplayNode, EdgeCap, Graph, Metadata, SettingsStyle};
+
+/// Margin around the graph in the resulting image.
+const MARGIN: f32 = 10.;
+
+/// Renders the graph to an SVG document.
+///
+/// Shapes are generated by the same [`DisplayNode`] and [`DisplayEdge`] implementations the widget uses,
+/// so nodes and edges are positioned with the zoom and pan from `meta` and styled according to `style`.
+/// Elements which can not be represented in SVG (meshes and paint callbacks) are skipped.
+///
+/// # Example
+/// ```
+/// use egui_graphs::{export::to_svg, Graph, Metadata, SettingsStyle};
+/// use petgraph::stable_graph::StableGraph;
+///
+/// let mut g = StableGraph::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, ());
+///
+/// let svg = to_svg(&Graph::<()>::from(&g), &SettingsStyle::default(), &Metadata::default());
+///
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<circle").count(), 2);
+/// ```
+pub fn to_svg<N, E, Ty, Ix, Dn, De>(
+    g: &Graph<N, E, Ty, Ix, Dn, De>,
+    style: &SettingsStyle,
+    meta: &Metadata,
+) -> String
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType + Clone,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    let shapes = super::shapes(&Context::default(), g, style, meta);
+
+    let bounds = shapes
+        .iter()
+        .map(Shape::visual_bounding_rect)
+        .filter(Rect::is_finite)
+        .fold(Rect::NOTHING, Rect::union);
+    let bounds = if bounds.is_positive() {
+        bounds.expand(MARGIN)
+    } else {
+        Rect::from_min_max(Pos2::ZERO, Pos2::new(MARGIN, MARGIN))
+    };
+
+    let background = style
+        .background_color
+        .map(|color| element(&Shape::rect_filled(bounds, 0., color), style.edge_cap));
+    let body = background
+        .into_iter()
+        .chain(shapes.iter().map(|s| element(s, style.edge_cap)))
+        .collect::<String>();
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"{x} {y} {w} {h}\">\n{body}</svg>\n",
+        x = bounds.min.x,
+        y = bounds.min.y,
+        w = bounds.width(),
+        h = bounds.height(),
+    )
+}
+
+/// Converts the shape to SVG elements, lines end with the `cap` edges are drawn with.
+fn element(shape: &Shape, cap: EdgeCap) -> String {
+    match shape {
+        Shape::Vec(shapes) => shapes.iter().map(|s| element(s, cap)).collect(),
+        Shape::Circle(c) => format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {} {}/>\n",
+            c.center.x,
+            c.center.y,
+            c.radius,
+            fill(c.fill),
+            stroke(c.stroke.width, c.stroke.color, cap),
+        ),
+        Shape::Ellipse(e) => format!(
+            "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\" {} {}/>\n",
+            e.center.x,
+            e.center.y,
+            e.radius.x,
+            e.radius.y,
+            fill(e.fill),
+            stroke(e.stroke.width, e.stroke.color, cap),
+        ),
+        Shape::LineSegment { points, stroke: s } => format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>\n",
+            points[0].x,
+            points[0].y,
+            points[1].x,
+            points[1].y,
+            stroke(s.width, solid(&s.color), cap),
+        ),
+        Shape::Path(p) => {
+            let tag = if p.closed { "polygon" } else { "polyline" };
+            let points = p
+                .points
+                .iter()
+                .map(|p| format!("{},{}", p.x, p.y))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "<{tag} points=\"{points}\" {} {}/>\n",
+                fill(p.fill),
+                stroke(p.stroke.width, solid(&p.stroke.color), cap),
+            )
+        }
+        Shape::Rect(r) => format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {} {}/>\n",
+            r.rect.min.x,
+            r.rect.min.y,
+            r.rect.width(),
+            r.rect.height(),
+            r.rounding.nw,
+            fill(r.fill),
+            stroke(r.stroke.width, r.stroke.color, cap),
+        ),
+        Shape::Text(t) => text(t),
+        Shape::QuadraticBezier(q) => {
+            let [p0, p1, p2] = q.points;
+            let d = format!("M {} {} Q {} {} {} {}", p0.x, p0.y, p1.x, p1.y, p2.x, p2.y);
+            path(
+                &d,
+                q.closed,
+                q.fill,
+                q.stroke.width,
+                solid(&q.stroke.color),
+                cap,
+            )
+        }
+        Shape::CubicBezier(c) => {
+            let [p0, p1, p2, p3] = c.points;
+            let d = format!(
+                "M {} {} C {} {} {} {} {} {}",
+                p0.x, p0.y, p1.x, p1.y, p2.x, p2.y, p3.x, p3.y
+            );
+            path(
+                &d,
+                c.closed,
+                c.fill,
+                c.stroke.width,
+                solid(&c.stroke.color),
+                cap,
+            )
+        }
+        Shape::Noop | Shape::Mesh(_) | Shape::Callback(_) => String::new(),
+    }
+}
+
+fn text(t: &TextShape) -> String {
+    let section = t.galley.job.sections.first();
+    let size = section.map_or(0., |s| s.format.font_id.size);
+    let color = t
+        .override_text_color
+        .unwrap_or_else(|| section.map_or(t.fallback_color, |s| s.format.color));
+
+    format!(
+        "<text x=\"{}\" y=\"{}\" font-size=\"{size}\" font-family=\"monospace\" dominant-baseline=\"hanging\" {}>{}</text>\n",
+        t.pos.x,
+        t.pos.y,
+        fill(color),
+        escape(t.galley.text()),
+    )
+}
+
+fn path(
+    d: &str,
+    closed: bool,
+    fill_color: Color32,
+    width: f32,
+    color: Color32,
+    cap: EdgeCap,
+) -> String {
+    let close = if closed { " Z" } else { "" };
+    format!(
+        "<path d=\"{d}{close}\" {} {}/>\n",
+        fill(fill_color),
+        stroke(width, color, cap),
+    )
+}
+
+fn solid(color: &ColorMode) -> Color32 {
+    match color {
+        ColorMode::Solid(c) => *c,
+        ColorMode::UV(_) => Stroke::default().color,
+    }
+}
+
+fn fill(color: Color32) -> String {
+    if color == Color32::TRANSPARENT {
+        return "fill=\"none\"".to_string();
+    }
+
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!(
+        "fill=\"#{r:02x}{g:02x}{b:02x}\" fill-opacity=\"{}\"",
+        f32::from(a) / 255.
+    )
+}
+
+fn stroke(width: f32, color: Color32, cap: EdgeCap) -> String {
+    if width <= 0. || color == Color32::TRANSPARENT {
+        return "stroke=\"none\"".to_string();
+    }
+
+    let linecap = match cap {
+        EdgeCap::Butt => "butt",
+        EdgeCap::Round => "round",
+    };
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!(
+        "stroke=\"#{r:02x}{g:02x}{b:02x}\" stroke-opacity=\"{}\" stroke-width=\"{width}\" stroke-linecap=\"{linecap}\"",
+        f32::from(a) / 255.
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill(Color32::TRANSPARENT), "fill=\"none\"");
+        assert_eq!(
+            fill(Color32::from_rgb(255, 0, 16)),
+            "fill=\"#ff0010\" fill-opacity=\"1\""
+        );
+    }
+
+    #[test]
+    fn test_stroke_empty() {
+        assert_eq!(stroke(0., Color32::RED, EdgeCap::Butt), "stroke=\"none\"");
+        assert_eq!(
+            stroke(1., Color32::TRANSPARENT, EdgeCap::Butt),
+            "stroke=\"none\""
+        );
+    }
+
+    #[test]
+    fn test_stroke_cap() {
+        assert!(stroke(1., Color32::RED, EdgeCap::Butt).ends_with("stroke-linecap=\"butt\""));
+        assert!(stroke(1., Color32::RED, EdgeCap::Round).ends_with("stroke-linecap=\"round\""));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+    }
+}
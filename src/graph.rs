@@ -1,3 +1,6 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use egui::Pos2;
 use petgraph::stable_graph::DefaultIx;
 use petgraph::Directed;
@@ -5,17 +8,36 @@ use petgraph::Directed;
 use petgraph::graph::IndexType;
 use petgraph::{
     stable_graph::{EdgeIndex, EdgeReference, NodeIndex, StableGraph},
-    visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences},
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable},
     Direction, EdgeType,
 };
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "events")]
+use crossbeam::channel::Sender;
+
 use crate::draw::{DisplayEdge, DisplayNode};
+#[cfg(feature = "events")]
+use crate::events::send_ignoring_disconnect;
+use crate::events::{
+    Event, PayloadEdgeDeselect, PayloadEdgeSelect, PayloadNodeDeselect, PayloadNodeDragEnd,
+    PayloadNodeDragStart, PayloadNodeMove, PayloadNodeSelect,
+};
+use crate::settings::SettingsInteraction;
 use crate::{metadata::Metadata, Edge, Node};
 use crate::{to_graph, DefaultEdgeShape, DefaultNodeShape};
 
+/// Source of graph generations, shared by all graphs so a generation identifies a single graph structure.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
 type StableGraphType<N, E, Ty, Ix, Dn, De> =
     StableGraph<Node<N, E, Ty, Ix, Dn>, Edge<N, E, Ty, Ix, Dn, De>, Ty, Ix>;
+type EdgeWithIndex<'a, N, E, Ty, Ix, Dn, De> = (EdgeIndex<Ix>, &'a Edge<N, E, Ty, Ix, Dn, De>);
 
 /// Wrapper around [`petgraph::stable_graph::StableGraph`] compatible with [`super::GraphView`].
 /// It is used to store graph data and provide access to it.
@@ -35,10 +57,16 @@ pub struct Graph<
     Dn: DisplayNode<N, E, Ty, Ix>,
     De: DisplayEdge<N, E, Ty, Ix, Dn>,
 {
+    /// The underlying stable graph. Adding or removing nodes or edges through it directly does not start
+    /// a new [`Graph::generation`], call [`Graph::invalidate`] afterwards or use [`Graph::stable_graph_mut`].
     pub g: StableGraphType<N, E, Ty, Ix, Dn, De>,
     selected_nodes: Vec<NodeIndex<Ix>>,
     selected_edges: Vec<EdgeIndex<Ix>>,
     dragged_node: Option<NodeIndex<Ix>>,
+    #[serde(skip)]
+    collapsed_into: HashMap<NodeIndex<Ix>, NodeIndex<Ix>>,
+    #[serde(skip, default = "next_generation")]
+    generation: u64,
 }
 
 impl<N, E, Ty, Ix, Dn, De> From<&StableGraph<N, E, Ty, Ix>> for Graph<N, E, Ty, Ix, Dn, De>
@@ -55,6 +83,21 @@ where
     }
 }
 
+impl<N, E, Ty, Ix, Dn, De> Default for Graph<N, E, Ty, Ix, Dn, De>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    /// Creates an empty graph.
+    fn default() -> Self {
+        Self::new(StableGraph::default())
+    }
+}
+
 impl<N, E, Ty, Ix, Dn, De> Graph<N, E, Ty, Ix, Dn, De>
 where
     N: Clone,
@@ -64,25 +107,139 @@ where
     Dn: DisplayNode<N, E, Ty, Ix>,
     De: DisplayEdge<N, E, Ty, Ix, Dn>,
 {
+    /// Wraps the stable graph as is. Nodes and edges are expected to have ids and orders set,
+    /// see [`Graph::from_stable_graph`].
     pub fn new(g: StableGraphType<N, E, Ty, Ix, Dn, De>) -> Self {
         Self {
             g,
             selected_nodes: Vec::default(),
             selected_edges: Vec::default(),
             dragged_node: Option::default(),
+            collapsed_into: HashMap::default(),
+            generation: next_generation(),
+        }
+    }
+
+    /// Wraps a stable graph of [`Node`]s and [`Edge`]s built elsewhere, e.g. when migrating existing petgraph code.
+    ///
+    /// Unlike [`Graph::new`], which expects ids and orders to be set already, it assigns every node and edge
+    /// its index as id and numbers parallel edges in index order, as [`Graph::add_edge`] does.
+    /// To convert a graph with plain payloads use [`Graph::from`] instead.
+    pub fn from_stable_graph(mut g: StableGraphType<N, E, Ty, Ix, Dn, De>) -> Self {
+        let nodes = g.node_indices().collect::<Vec<_>>();
+        for idx in nodes {
+            g[idx].set_id(idx);
+        }
+
+        let edges = g.edge_indices().collect::<Vec<_>>();
+        for idx in edges {
+            let Some((start, end)) = g.edge_endpoints(idx) else {
+                continue;
+            };
+            let order = g
+                .edges_connecting(start, end)
+                .filter(|e| e.id() < idx)
+                .count();
+            let e = &mut g[idx];
+            e.set_id(idx);
+            e.set_order(order);
         }
+
+        Self::new(g)
+    }
+
+    /// Returns the underlying stable graph for advanced changes not covered by methods of the graph.
+    ///
+    /// Ids and orders of added elements have to be set as [`Graph::from_stable_graph`] does. Every call starts
+    /// a new [`Graph::generation`].
+    pub fn stable_graph_mut(&mut self) -> &mut StableGraphType<N, E, Ty, Ix, Dn, De> {
+        self.g()
+    }
+
+    /// Identifies the current set of nodes and edges, changes whenever they are added or removed with methods
+    /// of the graph, [`Graph::g`] and [`Graph::stable_graph_mut`].
+    ///
+    /// [`crate::GraphView`] caches state derived from the structure only, i.e. connected component colors,
+    /// until the generation changes. Per node state, e.g. selection and locations, is synced every frame.
+    /// Changes made through the public `g` field are not tracked and require [`Graph::invalidate`].
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Starts a new generation. Callers must call it after adding or removing nodes or edges through the `g`
+    /// field directly, otherwise state cached for the previous structure, e.g. component colors, stays stale.
+    pub fn invalidate(&mut self) {
+        self.generation = next_generation();
     }
 
     /// Finds node by position. Can be optimized by using a spatial index like quad-tree if needed.
+    ///
+    /// When several nodes contain the position the one with the nearest center wins, remaining ties
+    /// are resolved in favor of the node drawn on top.
     pub fn node_by_screen_pos(&self, meta: &Metadata, screen_pos: Pos2) -> Option<NodeIndex<Ix>> {
+        self.find_node(meta, screen_pos, 0., |_| true)
+    }
+
+    /// The same as [`Graph::node_by_screen_pos`], but also finds nodes whose boundary is within `padding`
+    /// screen points from the position. Nodes containing the position take precedence, otherwise the closest one is returned.
+    pub fn node_by_screen_pos_with_padding(
+        &self,
+        meta: &Metadata,
+        screen_pos: Pos2,
+        padding: f32,
+    ) -> Option<NodeIndex<Ix>> {
+        self.find_node(meta, screen_pos, padding, |_| true)
+    }
+
+    /// The same as [`Graph::node_by_screen_pos_with_padding`], but skips nodes which are not interactable,
+    /// so the position resolves to the nodes below them.
+    pub fn interactable_node_by_screen_pos_with_padding(
+        &self,
+        meta: &Metadata,
+        screen_pos: Pos2,
+        padding: f32,
+    ) -> Option<NodeIndex<Ix>> {
+        self.find_node(meta, screen_pos, padding, Node::interactable)
+    }
+
+    fn find_node(
+        &self,
+        meta: &Metadata,
+        screen_pos: Pos2,
+        padding: f32,
+        filter: impl Fn(&Node<N, E, Ty, Ix, Dn>) -> bool,
+    ) -> Option<NodeIndex<Ix>> {
         let pos_in_graph = meta.screen_to_canvas_pos(screen_pos);
-        for (idx, node) in self.nodes_iter() {
-            let display = node.display();
-            if display.is_inside(pos_in_graph) {
-                return Some(idx);
-            }
+        let candidates = || {
+            self.nodes_iter()
+                .filter(|(idx, node)| !self.collapsed_into.contains_key(idx) && filter(node))
+        };
+
+        let found = candidates()
+            .filter(|(_, node)| node.display().is_inside(pos_in_graph))
+            .min_by(|(a_idx, a), (b_idx, b)| {
+                let a_dist = a.location().distance(pos_in_graph);
+                let b_dist = b.location().distance(pos_in_graph);
+                a_dist
+                    .total_cmp(&b_dist)
+                    .then_with(|| draw_rank(*b_idx, b).cmp(&draw_rank(*a_idx, a)))
+            })
+            .map(|(idx, _)| idx);
+        if found.is_some() || padding <= 0. {
+            return found;
         }
-        None
+
+        let max_dist = meta.screen_to_canvas_size(padding);
+        candidates()
+            .map(|(idx, node)| {
+                let boundary = node
+                    .display()
+                    .closest_boundary_point(pos_in_graph - node.location());
+                (idx, boundary.distance(pos_in_graph))
+            })
+            .filter(|(_, dist)| *dist <= max_dist)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
     }
 
     /// Finds edge by position.
@@ -90,7 +247,7 @@ where
     pub fn edge_by_screen_pos(&self, meta: &Metadata, screen_pos: Pos2) -> Option<EdgeIndex<Ix>> {
         let pos_in_graph = meta.screen_to_canvas_pos(screen_pos);
         for (idx, e) in self.edges_iter() {
-            let Some((idx_start, idx_end)) = self.g.edge_endpoints(e.id()) else {
+            let Some((idx_start, idx_end)) = self.visible_edge_endpoints(idx) else {
                 continue;
             };
             let start = self.g.node_weight(idx_start).unwrap();
@@ -103,7 +260,37 @@ where
         None
     }
 
+    /// The same as [`Graph::edge_by_screen_pos`], but also finds edges within `padding` screen points from the position.
+    /// Edges under the position take precedence over edges nearby.
+    ///
+    /// Padding is checked with [`crate::DisplayEdge::is_inside_with_padding`].
+    #[allow(clippy::missing_panics_doc)] // TODO: add panics doc
+    pub fn edge_by_screen_pos_with_padding(
+        &self,
+        meta: &Metadata,
+        screen_pos: Pos2,
+        padding: f32,
+    ) -> Option<EdgeIndex<Ix>> {
+        let found = self.edge_by_screen_pos(meta, screen_pos);
+        if found.is_some() || padding <= 0. {
+            return found;
+        }
+
+        let pos_in_graph = meta.screen_to_canvas_pos(screen_pos);
+        let padding = meta.screen_to_canvas_size(padding);
+        self.edges_iter().find_map(|(idx, e)| {
+            let (idx_start, idx_end) = self.visible_edge_endpoints(idx)?;
+            let start = self.g.node_weight(idx_start).unwrap();
+            let end = self.g.node_weight(idx_end).unwrap();
+            e.display()
+                .is_inside_with_padding(start, end, pos_in_graph, padding)
+                .then_some(idx)
+        })
+    }
+
+    /// Returns the underlying graph for direct changes, which are assumed to change the structure.
     pub fn g(&mut self) -> &mut StableGraphType<N, E, Ty, Ix, Dn, De> {
+        self.invalidate();
         &mut self.g
     }
 
@@ -113,25 +300,30 @@ where
         let node = Node::new(payload);
 
         let idx = self.g.add_node(node);
+        self.invalidate();
         let graph_node = self.g.node_weight_mut(idx).unwrap();
 
         graph_node.set_id(idx);
-        graph_node.set_location(Pos2::default());
+        graph_node.set_dirty(true);
+        graph_node.set_layout_location(Pos2::default());
         graph_node.set_label(idx.index().to_string());
 
         idx
     }
 
-    /// Adds node to graph setting custom location and default label value
+    /// Adds node to graph setting custom location and default label value.
+    /// Layouts start from the location and may move the node, pin it with [`Node::set_location`].
     #[allow(clippy::missing_panics_doc)] // TODO: add panics doc
     pub fn add_node_with_location(&mut self, payload: N, location: Pos2) -> NodeIndex<Ix> {
         let node = Node::new(payload);
 
         let idx = self.g.add_node(node);
+        self.invalidate();
         let graph_node = self.g.node_weight_mut(idx).unwrap();
 
         graph_node.set_id(idx);
-        graph_node.set_location(location);
+        graph_node.set_dirty(true);
+        graph_node.set_layout_location(location);
         graph_node.set_label(idx.index().to_string());
 
         idx
@@ -153,10 +345,12 @@ where
         let node = Node::new(payload);
 
         let idx = self.g.add_node(node);
+        self.invalidate();
         let graph_node = self.g.node_weight_mut(idx).unwrap();
 
         graph_node.set_id(idx);
-        graph_node.set_location(location);
+        graph_node.set_dirty(true);
+        graph_node.set_layout_location(location);
         graph_node.set_label(label);
 
         idx
@@ -171,9 +365,23 @@ where
             self.remove_edges_between(*n, idx);
         }
 
+        self.invalidate();
         self.g.remove_node(idx)
     }
 
+    /// Fades the node out and removes it together with its edges once it is fully transparent.
+    ///
+    /// Removal happens in [`crate::GraphView`] after the fade duration from [`crate::SettingsStyle`] passes.
+    /// Returns `false` if the node does not exist.
+    pub fn remove_node_faded(&mut self, idx: NodeIndex<Ix>) -> bool {
+        let Some(n) = self.g.node_weight_mut(idx) else {
+            return false;
+        };
+
+        n.fade_out_and_remove();
+        true
+    }
+
     /// Removes all edges between start and end node. Returns removed edges count.
     #[allow(clippy::missing_panics_doc)] // TODO: add panics doc
     pub fn remove_edges_between(&mut self, start: NodeIndex<Ix>, end: NodeIndex<Ix>) -> usize {
@@ -186,6 +394,7 @@ where
             return 0;
         }
 
+        self.invalidate();
         let mut removed = 0;
         for e in &idxs {
             self.g.remove_edge(*e).unwrap();
@@ -206,6 +415,7 @@ where
         let order = self.g.edges_connecting(start, end).count();
 
         let idx = self.g.add_edge(start, end, Edge::new(payload));
+        self.invalidate();
         let e = self.g.edge_weight_mut(idx).unwrap();
 
         e.set_id(idx);
@@ -227,6 +437,7 @@ where
         let order = self.g.edges_connecting(start, end).count();
 
         let idx = self.g.add_edge(start, end, Edge::new(payload));
+        self.invalidate();
         let e = self.g.edge_weight_mut(idx).unwrap();
 
         e.set_id(idx);
@@ -243,6 +454,7 @@ where
         let order = self.g.edge_weight(idx)?.order();
 
         let payload = self.g.remove_edge(idx)?;
+        self.invalidate();
 
         let siblings = self
             .g
@@ -273,6 +485,41 @@ where
             .map(|e| (e.id(), e.weight()))
     }
 
+    /// Returns all edges going from `start` to `end` sorted by their position among parallel edges.
+    ///
+    /// For directed graphs edges in opposite direction are not parallel, for undirected graphs
+    /// edges connecting the nodes in both directions are returned.
+    pub fn parallel_edges(&self, start: NodeIndex<Ix>, end: NodeIndex<Ix>) -> Vec<EdgeIndex<Ix>> {
+        let mut res = self
+            .edges_connecting(start, end)
+            .map(|(idx, e)| (idx, e.order()))
+            .collect::<Vec<_>>();
+        res.sort_by_key(|(_, order)| *order);
+
+        res.into_iter().map(|(idx, _)| idx).collect()
+    }
+
+    /// Returns all edges between the nodes in any direction in index order, e.g. to find reciprocal edges
+    /// of directed graphs. A self loop is returned once if `a` and `b` are the same node.
+    pub fn edges_between(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Vec<EdgeIndex<Ix>> {
+        let mut res = self
+            .g
+            .edges_connecting(a, b)
+            .chain(self.g.edges_connecting(b, a))
+            .map(|e| e.id())
+            .collect::<Vec<_>>();
+        res.sort();
+        res.dedup();
+
+        res
+    }
+
+    /// Returns position of the edge among its [`Graph::parallel_edges`], which defines curvature of the edge
+    /// when it is drawn. Returns `None` if the edge does not exist.
+    pub fn edge_ordinal(&self, idx: EdgeIndex<Ix>) -> Option<usize> {
+        self.edge(idx).map(Edge::order)
+    }
+
     /// Provides iterator over all nodes and their indices.
     pub fn nodes_iter(&self) -> impl Iterator<Item = (NodeIndex<Ix>, &Node<N, E, Ty, Ix, Dn>)> {
         self.g.node_references()
@@ -303,6 +550,16 @@ where
         self.g.edge_weight_mut(i)
     }
 
+    /// Sets weight of every edge extracted from it, e.g. from the payload.
+    ///
+    /// Edges added afterwards get the default weight `1.0`.
+    pub fn set_edge_weights(&mut self, weight: impl Fn(&Edge<N, E, Ty, Ix, Dn, De>) -> f32) {
+        for e in self.g.edge_weights_mut() {
+            let w = weight(e);
+            e.set_weight(w);
+        }
+    }
+
     pub fn is_directed(&self) -> bool {
         self.g.is_directed()
     }
@@ -319,6 +576,232 @@ where
         self.g.edges_directed(idx, dir)
     }
 
+    /// Returns all edges incident to the node with their indices, both outgoing and incoming for directed graphs.
+    /// Every self loop is returned once.
+    pub fn incident_edges(
+        &self,
+        idx: NodeIndex<Ix>,
+    ) -> Vec<EdgeWithIndex<'_, N, E, Ty, Ix, Dn, De>> {
+        let mut res = self
+            .g
+            .edges(idx)
+            .map(|e| (e.id(), e.weight()))
+            .collect::<Vec<_>>();
+        if self.is_directed() {
+            res.extend(
+                self.g
+                    .edges_directed(idx, Direction::Incoming)
+                    .filter(|e| e.source() != idx)
+                    .map(|e| (e.id(), e.weight())),
+            );
+        }
+
+        res
+    }
+
+    /// Returns unique indices of nodes connected to the node by an edge in any direction.
+    pub fn neighbors(&self, idx: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        let mut res = Vec::new();
+        for n in self.g.neighbors_undirected(idx) {
+            if !res.contains(&n) {
+                res.push(n);
+            }
+        }
+
+        res
+    }
+
+    /// Returns unique indices of nodes the node has edges to in the direction, `Incoming` for predecessors.
+    /// Direction is ignored for undirected graphs.
+    pub fn neighbors_directed(&self, idx: NodeIndex<Ix>, dir: Direction) -> Vec<NodeIndex<Ix>> {
+        let mut res = Vec::new();
+        for n in self.g.neighbors_directed(idx, dir) {
+            if !res.contains(&n) {
+                res.push(n);
+            }
+        }
+
+        res
+    }
+
+    /// Returns the node and all nodes reachable from it by at most `hops` edges in any direction.
+    pub fn neighborhood(&self, idx: NodeIndex<Ix>, hops: usize) -> HashSet<NodeIndex<Ix>> {
+        let mut res = HashSet::new();
+        if self.node(idx).is_none() {
+            return res;
+        }
+
+        res.insert(idx);
+        let mut frontier = vec![idx];
+        for _ in 0..hops {
+            let mut next = Vec::new();
+            for curr in frontier {
+                for n in self.g.neighbors_undirected(curr) {
+                    if res.insert(n) {
+                        next.push(n);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        res
+    }
+
+    /// Returns groups of nodes connected by edges in any direction, i.e. weakly connected components
+    /// for directed graphs. Components are ordered by their first node and nodes keep the graph order.
+    pub fn connected_components(&self) -> Vec<Vec<NodeIndex<Ix>>> {
+        let mut components = UnionFind::<usize>::new(self.g.node_bound());
+        for e in self.g.edge_references() {
+            components.union(e.source().index(), e.target().index());
+        }
+
+        let mut ordinals = HashMap::new();
+        let mut res: Vec<Vec<NodeIndex<Ix>>> = vec![];
+        for idx in self.g.node_indices() {
+            let root = components.find(idx.index());
+            let next = ordinals.len();
+            let ordinal = *ordinals.entry(root).or_insert(next);
+            if ordinal == res.len() {
+                res.push(vec![]);
+            }
+            res[ordinal].push(idx);
+        }
+
+        res
+    }
+
+    /// Returns nodes of the shortest path between the nodes along edges in any direction, both ends included.
+    /// Returns an empty vector if the nodes are not connected.
+    pub fn shortest_path(&self, from: NodeIndex<Ix>, to: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        if self.node(from).is_none() || self.node(to).is_none() {
+            return vec![];
+        }
+
+        let mut prev = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(curr) = queue.pop_front() {
+            if curr == to {
+                break;
+            }
+            for n in self.g.neighbors_undirected(curr) {
+                if let Entry::Vacant(e) = prev.entry(n) {
+                    e.insert(curr);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        if !prev.contains_key(&to) {
+            return vec![];
+        }
+
+        let mut res = vec![to];
+        let mut curr = to;
+        while curr != from {
+            curr = prev[&curr];
+            res.push(curr);
+        }
+        res.reverse();
+
+        res
+    }
+
+    /// Returns all nodes reachable from the node by outgoing edges, the node itself excluded.
+    pub fn descendants(&self, idx: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        let mut res = Vec::new();
+        let mut visited = HashSet::from([idx]);
+        let mut queue = VecDeque::from([idx]);
+        while let Some(curr) = queue.pop_front() {
+            for n in self.g.neighbors_directed(curr, Direction::Outgoing) {
+                if visited.insert(n) {
+                    res.push(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Collapses descendants of the node into it. Descendants are hidden and their edges to the rest
+    /// of the graph are drawn from the collapsed node. Locations of hidden nodes are kept, so expanding
+    /// the node restores them.
+    pub fn collapse(&mut self, idx: NodeIndex<Ix>) {
+        if let Some(n) = self.node_mut(idx) {
+            n.set_collapsed(true);
+        }
+        self.sync_collapsed();
+    }
+
+    /// Expands the collapsed node showing its descendants again.
+    pub fn expand(&mut self, idx: NodeIndex<Ix>) {
+        if let Some(n) = self.node_mut(idx) {
+            n.set_collapsed(false);
+        }
+        self.sync_collapsed();
+    }
+
+    /// Returns the collapsed node hiding the node, `None` if the node is visible.
+    pub fn collapsed_into(&self, idx: NodeIndex<Ix>) -> Option<NodeIndex<Ix>> {
+        self.collapsed_into.get(&idx).copied()
+    }
+
+    /// Returns endpoints of the edge as they are displayed, hidden endpoints are replaced with
+    /// collapsed nodes hiding them. Returns `None` if the edge does not exist or is hidden inside a collapsed node.
+    pub fn visible_edge_endpoints(
+        &self,
+        idx: EdgeIndex<Ix>,
+    ) -> Option<(NodeIndex<Ix>, NodeIndex<Ix>)> {
+        let (start, end) = self.edge_endpoints(idx)?;
+        let visible_start = self.collapsed_into(start).unwrap_or(start);
+        let visible_end = self.collapsed_into(end).unwrap_or(end);
+        if visible_start == visible_end && (visible_start != start || visible_end != end) {
+            return None;
+        }
+
+        Some((visible_start, visible_end))
+    }
+
+    /// Sets the size scale of nodes and updates displays of the changed ones, so their boundaries match
+    /// the current zoom before edges are drawn and nodes are hit tested.
+    pub(crate) fn sync_node_size_scale(&mut self, scale: Option<f32>) {
+        self.g.node_weights_mut().for_each(|n| {
+            if n.props().size_scale() == scale.unwrap_or(1.) {
+                return;
+            }
+
+            n.set_size_scale_computed(scale);
+            let props = n.props().clone();
+            n.display_mut().update(&props);
+        });
+    }
+
+    /// Recomputes nodes hidden by collapsed nodes.
+    pub(crate) fn sync_collapsed(&mut self) {
+        self.collapsed_into.clear();
+
+        let collapsed = self
+            .nodes_iter()
+            .filter(|(_, n)| n.collapsed())
+            .map(|(idx, _)| (idx, self.descendants(idx)))
+            .collect::<Vec<_>>();
+
+        for (root, descendants) in &collapsed {
+            // collapsed nodes hidden by other collapsed nodes keep their state until they are shown again
+            if collapsed
+                .iter()
+                .any(|(other, other_descendants)| other != root && other_descendants.contains(root))
+            {
+                continue;
+            }
+
+            for idx in descendants {
+                self.collapsed_into.insert(*idx, *root);
+            }
+        }
+    }
+
     pub fn selected_nodes(&self) -> &[NodeIndex<Ix>] {
         &self.selected_nodes
     }
@@ -335,6 +818,178 @@ where
         self.selected_edges = edges;
     }
 
+    /// Rounds location of every node to the nearest multiple of `grid`, e.g. to clean up imported positions
+    /// before the graph is shown. Does nothing if `grid` is not positive.
+    pub fn snap_positions(&mut self, grid: f32) -> LocationChange<Ix> {
+        let mut change = LocationChange { moved: vec![] };
+        if !grid.is_finite() || grid <= 0. {
+            return change;
+        }
+
+        for n in self.g.node_weights_mut() {
+            let loc = n.location();
+            let snapped = (loc.to_vec2() / grid).round().to_pos2() * grid;
+            if snapped == loc {
+                continue;
+            }
+            n.set_location(snapped);
+            change.moved.push((n.id(), loc, snapped));
+        }
+
+        change
+    }
+
+    /// Applies the state carried by a recorded event to the graph, e.g. to replay a session.
+    ///
+    /// Node moves, drags and selection of nodes and edges are applied. Camera, click, hover and structural
+    /// events do not describe graph state and are ignored. Returns false if the event was ignored or refers
+    /// to a missing element.
+    pub fn apply(&mut self, event: &Event) -> bool {
+        self.replay(event, false)
+    }
+
+    /// Undoes [`Graph::apply`] of the event restoring the state before it, e.g. to seek back in a replay.
+    pub fn revert(&mut self, event: &Event) -> bool {
+        self.replay(event, true)
+    }
+
+    fn replay(&mut self, event: &Event, revert: bool) -> bool {
+        let apply = !revert;
+        match event {
+            Event::NodeMove(PayloadNodeMove { id, diff, new_pos }) => {
+                let new = Pos2::from(*new_pos);
+                let loc = if revert {
+                    new - egui::Vec2::from(*diff)
+                } else {
+                    new
+                };
+                self.change_node(*id, |n| n.set_location(loc))
+            }
+            Event::NodeDragStart(PayloadNodeDragStart { id }) => {
+                self.change_node(*id, |n| n.set_dragged(apply))
+            }
+            Event::NodeDragEnd(PayloadNodeDragEnd { id, from, to }) => {
+                let loc = Pos2::from(if revert { *from } else { *to });
+                self.change_node(*id, |n| {
+                    n.set_location(loc);
+                    n.set_dragged(revert);
+                })
+            }
+            Event::NodeSelect(PayloadNodeSelect { id }) => {
+                self.change_node(*id, |n| n.set_selected(apply))
+            }
+            Event::NodeDeselect(PayloadNodeDeselect { id }) => {
+                self.change_node(*id, |n| n.set_selected(revert))
+            }
+            Event::EdgeSelect(PayloadEdgeSelect { id }) => self
+                .edge_mut(EdgeIndex::new(*id))
+                .map(|e| e.set_selected(apply))
+                .is_some(),
+            Event::EdgeDeselect(PayloadEdgeDeselect { id }) => self
+                .edge_mut(EdgeIndex::new(*id))
+                .map(|e| e.set_selected(revert))
+                .is_some(),
+            _ => false,
+        }
+    }
+
+    fn change_node(&mut self, id: usize, change: impl FnOnce(&mut Node<N, E, Ty, Ix, Dn>)) -> bool {
+        self.node_mut(NodeIndex::new(id)).map(change).is_some()
+    }
+
+    /// Selects all nodes and edges for which multiselection is enabled in `settings`.
+    ///
+    /// Like clicks in [`crate::GraphView`], skips non-interactable nodes and nodes hidden by collapsed nodes.
+    pub fn select_all(&mut self, settings: &SettingsInteraction) -> SelectionChange<Ix> {
+        self.change_selection(Self::multi_selection(settings), |_| true)
+    }
+
+    /// Deselects all nodes and edges for which selection is enabled in `settings`.
+    pub fn deselect_all(&mut self, settings: &SettingsInteraction) -> SelectionChange<Ix> {
+        let enabled = (
+            settings.node_selection_enabled || settings.node_selection_multi_enabled,
+            settings.edge_selection_enabled || settings.edge_selection_multi_enabled,
+        );
+        self.change_selection(enabled, |_| false)
+    }
+
+    /// Flips selection of all nodes and edges for which multiselection is enabled in `settings`.
+    ///
+    /// Nodes which can not be selected with a click, see [`Graph::select_all`], are only deselected.
+    pub fn invert_selection(&mut self, settings: &SettingsInteraction) -> SelectionChange<Ix> {
+        self.change_selection(Self::multi_selection(settings), |selected| !selected)
+    }
+
+    /// Whether nodes and edges can be selected in bulk, which would break single selection otherwise.
+    fn multi_selection(settings: &SettingsInteraction) -> (bool, bool) {
+        (
+            settings.node_selection_multi_enabled,
+            settings.edge_selection_multi_enabled,
+        )
+    }
+
+    fn change_selection(
+        &mut self,
+        (nodes, edges): (bool, bool),
+        selected: impl Fn(bool) -> bool,
+    ) -> SelectionChange<Ix> {
+        let mut change = SelectionChange {
+            selected_nodes: vec![],
+            deselected_nodes: vec![],
+            selected_edges: vec![],
+            deselected_edges: vec![],
+        };
+
+        if nodes {
+            for n in self.g.node_weights_mut() {
+                let idx = n.id();
+                let next = selected(n.selected());
+                if next == n.selected() {
+                    continue;
+                }
+                if next && (!n.interactable() || self.collapsed_into.contains_key(&idx)) {
+                    continue;
+                }
+
+                n.set_selected(next);
+                if next {
+                    change.selected_nodes.push(idx);
+                } else {
+                    change.deselected_nodes.push(idx);
+                }
+            }
+        }
+
+        if edges {
+            for e in self.g.edge_weights_mut() {
+                let next = selected(e.selected());
+                if next == e.selected() {
+                    continue;
+                }
+
+                e.set_selected(next);
+                if next {
+                    change.selected_edges.push(e.id());
+                } else {
+                    change.deselected_edges.push(e.id());
+                }
+            }
+        }
+
+        self.selected_nodes = self
+            .nodes_iter()
+            .filter(|(_, n)| n.selected())
+            .map(|(idx, _)| idx)
+            .collect();
+        self.selected_edges = self
+            .edges_iter()
+            .filter(|(_, e)| e.selected())
+            .map(|(idx, _)| idx)
+            .collect();
+
+        change
+    }
+
     pub fn dragged_node(&self) -> Option<NodeIndex<Ix>> {
         self.dragged_node
     }
@@ -351,3 +1006,498 @@ where
         self.g.node_count()
     }
 }
+
+/// Orders nodes the way they are painted by [`crate::GraphView`], greater ranks are drawn on top:
+/// selected and dragged nodes, then nodes with higher z, then nodes added later.
+fn draw_rank<N, E, Ty, Ix, D>(idx: NodeIndex<Ix>, n: &Node<N, E, Ty, Ix, D>) -> (bool, i32, usize)
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    D: DisplayNode<N, E, Ty, Ix>,
+{
+    (n.selected() || n.dragged(), n.z(), idx.index())
+}
+
+/// Nodes moved by [`Graph::snap_positions`] with their old and new locations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationChange<Ix: IndexType> {
+    pub moved: Vec<(NodeIndex<Ix>, Pos2, Pos2)>,
+}
+
+impl<Ix: IndexType> LocationChange<Ix> {
+    /// Whether no node moved.
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty()
+    }
+
+    #[cfg(feature = "events")]
+    /// Sends a move event for every moved node, the same events [`crate::GraphView`] sends when nodes are dragged.
+    ///
+    /// Like the widget, ignores a disconnected receiver.
+    pub fn publish(&self, sender: &Sender<Event>) {
+        for (idx, old, new) in &self.moved {
+            let event = Event::NodeMove(PayloadNodeMove {
+                id: idx.index(),
+                diff: (*new - *old).into(),
+                new_pos: [new.x, new.y],
+            });
+            send_ignoring_disconnect(sender, event);
+        }
+    }
+}
+
+/// Nodes and edges which changed selection after [`Graph::select_all`], [`Graph::deselect_all`]
+/// or [`Graph::invert_selection`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionChange<Ix: IndexType> {
+    pub selected_nodes: Vec<NodeIndex<Ix>>,
+    pub deselected_nodes: Vec<NodeIndex<Ix>>,
+    pub selected_edges: Vec<EdgeIndex<Ix>>,
+    pub deselected_edges: Vec<EdgeIndex<Ix>>,
+}
+
+impl<Ix: IndexType> SelectionChange<Ix> {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.selected_nodes.is_empty()
+            && self.deselected_nodes.is_empty()
+            && self.selected_edges.is_empty()
+            && self.deselected_edges.is_empty()
+    }
+
+    #[cfg(feature = "events")]
+    /// Sends select and deselect events for the changed nodes and edges, the same events
+    /// [`crate::GraphView`] sends when selection changes on click.
+    ///
+    /// Like the widget, ignores a disconnected receiver.
+    pub fn publish(&self, sender: &Sender<Event>) {
+        let nodes = self
+            .selected_nodes
+            .iter()
+            .map(|idx| Event::NodeSelect(PayloadNodeSelect { id: idx.index() }))
+            .chain(
+                self.deselected_nodes
+                    .iter()
+                    .map(|idx| Event::NodeDeselect(PayloadNodeDeselect { id: idx.index() })),
+            );
+        let edges = self
+            .selected_edges
+            .iter()
+            .map(|idx| Event::EdgeSelect(PayloadEdgeSelect { id: idx.index() }))
+            .chain(
+                self.deselected_edges
+                    .iter()
+                    .map(|idx| Event::EdgeDeselect(PayloadEdgeDeselect { id: idx.index() })),
+            );
+
+        for event in nodes.chain(edges) {
+            send_ignoring_disconnect(sender, event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::graph;
+
+    #[test]
+    fn test_from_stable_graph() {
+        let mut sg = StableGraph::new();
+        let a = sg.add_node(Node::new(()));
+        let b = sg.add_node(Node::new(()));
+        sg.add_edge(a, b, Edge::new(()));
+        let parallel = sg.add_edge(a, b, Edge::new(()));
+
+        let g = Graph::<()>::from_stable_graph(sg);
+        assert_eq!(g.node(b).unwrap().id(), b);
+        assert_eq!(g.edge(parallel).unwrap().id(), parallel);
+        assert_eq!(g.edge(parallel).unwrap().order(), 1);
+        assert_eq!(Graph::<()>::default().node_count(), 0);
+    }
+
+    #[test]
+    fn test_snap_positions() {
+        let mut g = graph();
+        let (a, b) = (NodeIndex::new(0), NodeIndex::new(1));
+        g.node_mut(a).unwrap().set_location(Pos2::new(9.8, -10.3));
+        g.node_mut(b).unwrap().set_location(Pos2::new(20., 30.));
+
+        let change = g.snap_positions(10.);
+        assert_eq!(g.node(a).unwrap().location(), Pos2::new(10., -10.));
+        assert_eq!(
+            change.moved,
+            vec![(a, Pos2::new(9.8, -10.3), Pos2::new(10., -10.))]
+        );
+        assert!(g.snap_positions(10.).is_empty());
+        assert!(g.snap_positions(0.).is_empty());
+    }
+
+    #[test]
+    fn test_apply_and_revert() {
+        let mut g = graph();
+        let a = NodeIndex::new(0);
+        let moved = Event::NodeMove(PayloadNodeMove {
+            id: 0,
+            diff: [1., 2.],
+            new_pos: [1., 2.],
+        });
+        let selected = Event::NodeSelect(PayloadNodeSelect { id: 0 });
+
+        assert!(g.apply(&moved));
+        assert!(g.apply(&selected));
+        assert_eq!(g.node(a).unwrap().location(), Pos2::new(1., 2.));
+        assert!(g.node(a).unwrap().selected());
+
+        assert!(g.revert(&selected));
+        assert!(g.revert(&moved));
+        assert_eq!(g.node(a).unwrap().location(), Pos2::ZERO);
+        assert!(!g.node(a).unwrap().selected());
+
+        assert!(!g.apply(&Event::NodeSelect(PayloadNodeSelect { id: 5 })));
+        assert!(!g.apply(&Event::Ready));
+    }
+
+    #[test]
+    fn test_edges_between_and_neighbors_directed() {
+        let mut g = graph();
+        let (a, b) = (NodeIndex::new(0), NodeIndex::new(1));
+        let back = g.add_edge(b, a, ());
+        g.add_edge(a, a, ());
+
+        assert_eq!(g.edges_between(b, a), vec![EdgeIndex::new(0), back]);
+        assert_eq!(g.edges_between(a, a).len(), 1);
+
+        let lonely = g.add_node(());
+        g.add_edge(lonely, b, ());
+        assert_eq!(g.neighbors_directed(b, Direction::Outgoing), vec![a]);
+        let mut incoming = g.neighbors_directed(b, Direction::Incoming);
+        incoming.sort();
+        assert_eq!(incoming, vec![a, lonely]);
+    }
+
+    #[test]
+    fn test_generation() {
+        let mut g = graph();
+        let other = graph();
+        assert_ne!(g.generation(), other.generation());
+
+        let generation = g.generation();
+        g.node_mut(NodeIndex::new(0)).unwrap().set_selected(true);
+        assert_eq!(g.generation(), generation);
+
+        let idx = g.add_node(());
+        assert_ne!(g.generation(), generation);
+
+        let generation = g.generation();
+        g.remove_node(idx);
+        assert_ne!(g.generation(), generation);
+
+        let generation = g.generation();
+        g.invalidate();
+        assert_ne!(g.generation(), generation);
+    }
+
+    #[test]
+    fn test_node_by_screen_pos_overlapping() {
+        let mut g = graph();
+        let (a, b) = (NodeIndex::new(0), NodeIndex::new(1));
+        let meta = Metadata::default();
+        let place = |g: &mut Graph, idx, loc| {
+            let n = g.node_mut(idx).unwrap();
+            n.set_location(loc);
+            let props = n.props().clone();
+            DisplayNode::<(), (), Directed, DefaultIx>::update(n.display_mut(), &props);
+        };
+        place(&mut g, a, Pos2::ZERO);
+        place(&mut g, b, Pos2::ZERO);
+
+        // coincident nodes: the one drawn on top wins
+        assert_eq!(g.node_by_screen_pos(&meta, Pos2::ZERO), Some(b));
+        g.node_mut(a).unwrap().set_z(1);
+        assert_eq!(g.node_by_screen_pos(&meta, Pos2::ZERO), Some(a));
+        g.node_mut(b).unwrap().set_selected(true);
+        assert_eq!(g.node_by_screen_pos(&meta, Pos2::ZERO), Some(b));
+
+        // nearest center wins regardless of draw order
+        place(&mut g, a, Pos2::new(4., 0.));
+        assert_eq!(g.node_by_screen_pos(&meta, Pos2::new(3., 0.)), Some(a));
+        assert_eq!(g.node_by_screen_pos(&meta, Pos2::new(1., 0.)), Some(b));
+
+        // non-interactable nodes let the position through to the nodes below
+        g.node_mut(b).unwrap().set_interactable(false);
+        assert_eq!(
+            g.interactable_node_by_screen_pos_with_padding(&meta, Pos2::new(1., 0.), 0.),
+            Some(a)
+        );
+        assert_eq!(
+            g.interactable_node_by_screen_pos_with_padding(&meta, Pos2::new(-4., 0.), 0.),
+            None
+        );
+    }
+
+    #[test]
+    fn test_incident_edges_and_neighbors() {
+        let mut sg = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        let c = sg.add_node(());
+        let ab = sg.add_edge(a, b, ());
+        let ca = sg.add_edge(c, a, ());
+        let aa = sg.add_edge(a, a, ());
+        let ba = sg.add_edge(b, a, ());
+        let g: Graph = Graph::from(&sg);
+
+        let mut edges = g
+            .incident_edges(a)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, vec![ab, ca, aa, ba]);
+
+        let mut neighbors = g.neighbors(a);
+        neighbors.sort();
+        assert_eq!(neighbors, vec![a, b, c]);
+        assert_eq!(g.neighbors(c), vec![a]);
+    }
+
+    #[test]
+    fn test_collapse_and_expand() {
+        let mut sg = StableGraph::new();
+        let root = sg.add_node(());
+        let child = sg.add_node(());
+        let grandchild = sg.add_node(());
+        let other = sg.add_node(());
+        let inner = sg.add_edge(root, child, ());
+        sg.add_edge(child, grandchild, ());
+        let outer = sg.add_edge(other, grandchild, ());
+        let mut g: Graph = Graph::from(&sg);
+
+        g.collapse(child);
+        g.collapse(root);
+        assert_eq!(g.collapsed_into(child), Some(root));
+        assert_eq!(g.collapsed_into(grandchild), Some(root));
+        assert_eq!(g.collapsed_into(other), None);
+        assert_eq!(g.visible_edge_endpoints(inner), None);
+        assert_eq!(g.visible_edge_endpoints(outer), Some((other, root)));
+
+        g.expand(root);
+        assert_eq!(g.collapsed_into(child), None);
+        assert_eq!(g.collapsed_into(grandchild), Some(child));
+        assert_eq!(g.visible_edge_endpoints(outer), Some((other, child)));
+        assert_eq!(g.visible_edge_endpoints(inner), Some((root, child)));
+    }
+
+    #[test]
+    fn test_node_by_screen_pos_with_padding() {
+        let mut g = graph();
+        let n = g.node_mut(NodeIndex::new(1)).unwrap();
+        n.set_location(Pos2::new(100., 0.));
+        let props = n.props().clone();
+        DisplayNode::<(), (), Directed, DefaultIx>::update(n.display_mut(), &props);
+        let mut meta = Metadata::default();
+        meta.zoom = 2.;
+
+        // default node radius is 5, so the boundary of the second node is 10 screen points away
+        let pos = Pos2::new(180., 0.);
+        assert_eq!(g.node_by_screen_pos(&meta, pos), None);
+        assert_eq!(g.node_by_screen_pos_with_padding(&meta, pos, 5.), None);
+        assert_eq!(
+            g.node_by_screen_pos_with_padding(&meta, pos, 12.),
+            Some(NodeIndex::new(1))
+        );
+    }
+
+    #[test]
+    fn test_edge_by_screen_pos_with_padding() {
+        let mut g = graph();
+        g.node_mut(NodeIndex::new(0))
+            .unwrap()
+            .set_location(Pos2::new(0., 0.));
+        g.node_mut(NodeIndex::new(1))
+            .unwrap()
+            .set_location(Pos2::new(100., 0.));
+        let mut meta = Metadata::default();
+        meta.zoom = 2.;
+
+        // default edge width is 2, so the edge is 6 screen points farther than the stroke
+        let pos = Pos2::new(100., 10.);
+        assert_eq!(g.edge_by_screen_pos(&meta, pos), None);
+        assert_eq!(g.edge_by_screen_pos_with_padding(&meta, pos, 4.), None);
+        assert_eq!(
+            g.edge_by_screen_pos_with_padding(&meta, pos, 8.),
+            Some(EdgeIndex::new(0))
+        );
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let mut g = graph();
+        let (a, b) = (NodeIndex::new(0), NodeIndex::new(1));
+        let first = EdgeIndex::new(0);
+        let second = g.add_edge(a, b, ());
+        let third = g.add_edge(a, b, ());
+        let back = g.add_edge(b, a, ());
+
+        assert_eq!(g.parallel_edges(a, b), vec![first, second, third]);
+        assert_eq!(g.parallel_edges(b, a), vec![back]);
+        assert_eq!(g.edge_ordinal(third), Some(2));
+
+        g.remove_edge(second);
+        assert_eq!(g.parallel_edges(a, b), vec![first, third]);
+        assert_eq!(g.edge_ordinal(third), Some(1));
+        assert_eq!(g.edge_ordinal(second), None);
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let mut sg = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        let c = sg.add_node(());
+        let far = sg.add_node(());
+        sg.add_edge(b, a, ());
+        sg.add_edge(b, c, ());
+        sg.add_edge(c, far, ());
+        let g: Graph = Graph::from(&sg);
+
+        assert_eq!(g.neighborhood(a, 0), HashSet::from([a]));
+        assert_eq!(g.neighborhood(a, 2), HashSet::from([a, b, c]));
+        assert_eq!(g.neighborhood(a, 5), HashSet::from([a, b, c, far]));
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut sg = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        let c = sg.add_node(());
+        let lonely = sg.add_node(());
+        let far = sg.add_node(());
+        sg.add_edge(a, c, ());
+        sg.add_edge(far, b, ());
+        let g: Graph = Graph::from(&sg);
+
+        assert_eq!(
+            g.connected_components(),
+            vec![vec![a, c], vec![b, far], vec![lonely]]
+        );
+        assert!(Graph::<()>::from(&StableGraph::new())
+            .connected_components()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let mut sg = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        let c = sg.add_node(());
+        let far = sg.add_node(());
+        let lonely = sg.add_node(());
+        sg.add_edge(a, b, ());
+        sg.add_edge(c, b, ());
+        sg.add_edge(c, far, ());
+        sg.add_edge(a, far, ());
+        let g: Graph = Graph::from(&sg);
+
+        assert_eq!(g.shortest_path(a, a), vec![a]);
+        assert_eq!(g.shortest_path(a, c).len(), 3);
+        assert_eq!(g.shortest_path(b, far).len(), 3);
+        assert_eq!(g.shortest_path(far, a), vec![far, a]);
+        assert!(g.shortest_path(a, lonely).is_empty());
+    }
+
+    #[test]
+    fn test_invert_selection() {
+        let mut g = graph();
+        g.node_mut(NodeIndex::new(0)).unwrap().set_selected(true);
+        let settings = SettingsInteraction::new().with_node_selection_multi_enabled(true);
+
+        let change = g.invert_selection(&settings);
+
+        assert_eq!(change.selected_nodes, vec![NodeIndex::new(1)]);
+        assert_eq!(change.deselected_nodes, vec![NodeIndex::new(0)]);
+        assert!(change.selected_edges.is_empty());
+        assert_eq!(g.selected_nodes(), &[NodeIndex::new(1)]);
+    }
+
+    #[test]
+    fn test_select_and_deselect_all() {
+        let mut g = graph();
+        let settings = SettingsInteraction::new()
+            .with_node_selection_multi_enabled(true)
+            .with_edge_selection_multi_enabled(true);
+
+        let change = g.select_all(&settings);
+        assert_eq!(change.selected_nodes.len(), 2);
+        assert_eq!(change.selected_edges.len(), 1);
+        assert!(g.select_all(&settings).is_empty());
+
+        let change = g.deselect_all(&settings);
+        assert_eq!(change.deselected_nodes.len(), 2);
+        assert_eq!(change.deselected_edges.len(), 1);
+        assert!(g.selected_nodes().is_empty());
+        assert!(g.selected_edges().is_empty());
+    }
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_publish_without_receiver() {
+        let mut g = graph();
+        let settings = SettingsInteraction::new().with_node_selection_multi_enabled(true);
+        let change = g.select_all(&settings);
+
+        let (sender, receiver) = crossbeam::channel::unbounded();
+        change.publish(&sender);
+        assert_eq!(receiver.len(), 2);
+
+        drop(receiver);
+        change.publish(&sender);
+        g.snap_positions(10.).publish(&sender);
+    }
+
+    #[test]
+    fn test_selection_disabled() {
+        let mut g = graph();
+
+        assert!(g.select_all(&SettingsInteraction::new()).is_empty());
+        assert!(g.selected_nodes().is_empty());
+    }
+
+    #[test]
+    fn test_select_all_requires_multiselection() {
+        let mut g = graph();
+        let single = SettingsInteraction::new()
+            .with_node_selection_enabled(true)
+            .with_edge_selection_enabled(true);
+
+        assert!(g.select_all(&single).is_empty());
+        assert!(g.invert_selection(&single).is_empty());
+
+        // clearing the selection does not break single selection
+        g.node_mut(NodeIndex::new(0)).unwrap().set_selected(true);
+        assert_eq!(g.deselect_all(&single).deselected_nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_select_all_skips_nodes_not_selectable_by_click() {
+        let mut g = graph();
+        let (a, b) = (NodeIndex::new(0), NodeIndex::new(1));
+        g.node_mut(a).unwrap().set_interactable(false);
+        let settings = SettingsInteraction::new().with_node_selection_multi_enabled(true);
+
+        assert_eq!(g.select_all(&settings).selected_nodes, vec![b]);
+
+        // hidden nodes are not selected, but are still deselected
+        g.node_mut(a).unwrap().set_interactable(true);
+        g.collapse(a);
+        let change = g.invert_selection(&settings);
+        assert_eq!(change.selected_nodes, vec![a]);
+        assert_eq!(change.deselected_nodes, vec![b]);
+        assert!(g.invert_selection(&settings).selected_nodes.is_empty());
+    }
+}
@@ -1,20 +1,46 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use crate::{
-    draw::{DefaultEdgeShape, DefaultNodeShape, DrawContext, Drawer},
+    draw::{
+        bundle, component_color, draw_scale_bar, normalize, DefaultEdgeShape, DefaultNodeShape,
+        DrawContext, Drawer,
+    },
     layouts::{self, Layout, LayoutState},
     metadata::Metadata,
-    settings::{SettingsInteraction, SettingsNavigation, SettingsStyle},
-    DisplayEdge, DisplayNode, Graph,
+    settings::{
+        FitToScreen, FollowOnPan, OnResize, SettingsInteraction, SettingsNavigation, SettingsStyle,
+    },
+    Colormap, DisplayEdge, DisplayNode, Edge, Graph, Node, Palette,
 };
 
-use egui::{Id, PointerButton, Pos2, Rect, Response, Sense, Ui, Vec2, Widget};
+use egui::{
+    Align2, Color32, CursorIcon, EventFilter, FontId, Id, Key, Modifiers, Painter, PointerButton,
+    Pos2, Rect, Response, Sense, Ui, Vec2, Widget,
+};
 
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use petgraph::{graph::EdgeIndex, stable_graph::DefaultIx};
 use petgraph::{graph::IndexType, Directed};
 use petgraph::{stable_graph::NodeIndex, EdgeType};
 
 const KEY_LAYOUT: &str = "egui_grpahs_layout";
+/// Relative change of the widget size on any axis which counts as resize.
+const RESIZE_THRESHOLD: f32 = 0.02;
+/// Size in canvas units fitted to the screen when the fitted bounds are empty or a single node.
+const DEFAULT_FIT_SIZE: Vec2 = Vec2::new(1., 100.);
+
+/// Nodes the pointer interacted with in a frame, see [`GraphView::graph_response`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphResponse<Ix: IndexType = DefaultIx> {
+    /// Node clicked once in the frame. Resolved after the double click delay with click disambiguation.
+    pub clicked_node: Option<NodeIndex<Ix>>,
+    pub double_clicked_node: Option<NodeIndex<Ix>>,
+    /// Node under the pointer after the hover delay.
+    pub hovered_node: Option<NodeIndex<Ix>>,
+    pub dragged_node: Option<NodeIndex<Ix>>,
+}
 
 pub type DefaultGraphView<'a> = GraphView<
     'a,
@@ -28,14 +54,29 @@ pub type DefaultGraphView<'a> = GraphView<
     layouts::random::Random,
 >;
 
+type NodeContextMenu<'a, N, E, Ty, Ix, Nd> =
+    Box<dyn FnMut(&mut Ui, NodeIndex<Ix>, &Node<N, E, Ty, Ix, Nd>) + 'a>;
+type CanvasContextMenu<'a> = Box<dyn FnMut(&mut Ui, Pos2) + 'a>;
+type EdgeTooltip<'a, N, E, Ty, Ix, Nd, Ed> =
+    Box<dyn FnMut(&mut Ui, EdgeIndex<Ix>, &Edge<N, E, Ty, Ix, Nd, Ed>) + 'a>;
+type NodeCategoryColor<'a, N> = Box<dyn Fn(&N) -> Color32 + 'a>;
+type NodeValueColor<'a, N> = (Colormap, Box<dyn Fn(&N) -> f32 + 'a>);
+type NodeAlpha<'a, N, E, Ty, Ix, Nd> = Box<dyn Fn(&Node<N, E, Ty, Ix, Nd>) -> f32 + 'a>;
+type EdgeCurvature<'a, E> = Box<dyn Fn(&E) -> f32 + 'a>;
+type Overlay<'a> = Box<dyn FnMut(&Painter, &Metadata) + 'a>;
+
 #[cfg(feature = "events")]
+use crate::events::send_ignoring_disconnect;
 use crate::events::{
-    Event, PayloadEdgeClick, PayloadEdgeDeselect, PayloadEdgeSelect, PayloadNodeClick,
+    Event, PayloadCanvasClick, PayloadCanvasDoubleClick, PayloadEdgeClick, PayloadEdgeCreate,
+    PayloadEdgeDelete, PayloadEdgeDeselect, PayloadEdgeDoubleClick, PayloadEdgeHover,
+    PayloadEdgeSelect, PayloadFitToScreen, PayloadNodeClick, PayloadNodeDelete,
     PayloadNodeDeselect, PayloadNodeDoubleClick, PayloadNodeDragEnd, PayloadNodeDragStart,
     PayloadNodeMove, PayloadNodeSelect, PayloadPan, PayloadZoom,
 };
 #[cfg(feature = "events")]
 use crossbeam::channel::Sender;
+use std::cell::RefCell;
 
 /// Widget for visualizing and interacting with graphs.
 ///
@@ -78,8 +119,19 @@ pub struct GraphView<
     settings_navigation: SettingsNavigation,
     settings_style: SettingsStyle,
 
+    node_context_menu: Option<NodeContextMenu<'a, N, E, Ty, Ix, Nd>>,
+    canvas_context_menu: Option<CanvasContextMenu<'a>>,
+    edge_tooltip: Option<EdgeTooltip<'a, N, E, Ty, Ix, Nd, Ed>>,
+    overlay: Option<Overlay<'a>>,
+    node_category_color: Option<NodeCategoryColor<'a, N>>,
+    node_value_color: Option<NodeValueColor<'a, N>>,
+    node_alpha: Option<NodeAlpha<'a, N, E, Ty, Ix, Nd>>,
+    edge_curvature: Option<EdgeCurvature<'a, E>>,
+    graph_response: GraphResponse<Ix>,
+
     #[cfg(feature = "events")]
     events_publisher: Option<&'a Sender<Event>>,
+    events_buffer: Option<RefCell<&'a mut Vec<Event>>>,
 
     _marker: PhantomData<(Nd, Ed, L, S)>,
 }
@@ -96,16 +148,32 @@ where
     L: Layout<S>,
 {
     fn ui(self, ui: &mut Ui) -> Response {
-        self.sync_layout(ui);
-
+        self.graph_response = GraphResponse::default();
         let mut meta = Metadata::load(ui);
+        let layout_changed = !meta.batch && self.sync_layout(ui);
+
+        let (zoom, pan, bounds) = (meta.zoom, meta.pan, meta.graph_bounds());
+        let fading = self.handle_fades(ui);
         self.sync_state(&mut meta);
 
+        let visuals = self.settings_style.visuals(ui.visuals());
         let (resp, p) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        self.handle_resize(&resp, &mut meta);
         self.handle_fit_to_screen(&resp, &mut meta);
-        self.handle_navigation(ui, &resp, &mut meta);
-        self.handle_node_drag(&resp, &mut meta);
-        self.handle_click(&resp, &mut meta);
+        self.sync_node_size_scale(&meta);
+        self.handle_edge_creation(ui, &resp, &mut meta);
+        let panned = self.handle_navigation(ui, &resp, &mut meta);
+        self.handle_follow(&resp, panned, &mut meta);
+        self.sync_node_size_scale(&meta);
+        let drag_click = self.handle_node_drag(&resp, &mut meta);
+        self.handle_click(&resp, drag_click, &mut meta);
+        self.handle_focus(&resp);
+        self.handle_keyboard(ui, &resp, &mut meta);
+        self.handle_node_deletion(ui, &resp);
+        self.handle_context_menu(&resp, &mut meta);
+        self.handle_hover(&resp, &mut meta);
+        self.show_edge_tooltip(ui, &resp, &meta);
+        self.sync_bundling(&mut meta);
 
         Drawer::<N, E, Ty, Ix, Nd, Ed, S, L>::new(
             self.g,
@@ -115,14 +183,50 @@ where
                 meta: &meta,
                 is_directed: self.g.is_directed(),
                 style: &self.settings_style,
+                visuals: &visuals,
             },
         )
         .draw();
 
-        meta.first_frame = false;
+        if let Some(overlay) = self.overlay.as_mut() {
+            overlay(&p, &meta);
+        }
+
+        if self.settings_style.scale_bar {
+            draw_scale_bar(&p, resp.rect, meta.zoom, &visuals);
+        }
+
+        if let Some(text) = &self.settings_style.empty_text {
+            if self.g.node_count() == 0 {
+                p.text(
+                    resp.rect.center(),
+                    Align2::CENTER_CENTER,
+                    text,
+                    FontId::proportional(14.),
+                    visuals.weak_text_color(),
+                );
+            }
+        }
+
+        let changed = meta.first_frame
+            || meta.zoom != zoom
+            || meta.pan != pan
+            || meta.graph_bounds() != bounds
+            || self.g.dragged_node().is_some()
+            || meta.edge_creation.is_some()
+            || layout_changed
+            || fading;
+
+        if meta.first_frame {
+            meta.first_frame = false;
+            self.set_ready();
+        }
         meta.save(ui);
+        self.graph_response.dragged_node = self.g.dragged_node();
 
-        ui.ctx().request_repaint();
+        if self.settings_navigation.continuous_repaint || changed {
+            ui.ctx().request_repaint();
+        }
 
         resp
     }
@@ -149,28 +253,147 @@ where
             settings_interaction: SettingsInteraction::default(),
             settings_navigation: SettingsNavigation::default(),
 
+            node_context_menu: Option::default(),
+            canvas_context_menu: Option::default(),
+            edge_tooltip: Option::default(),
+            overlay: Option::default(),
+            node_category_color: Option::default(),
+            node_value_color: Option::default(),
+            node_alpha: Option::default(),
+            edge_curvature: Option::default(),
+            graph_response: GraphResponse::default(),
+
             #[cfg(feature = "events")]
             events_publisher: Option::default(),
+            events_buffer: Option::default(),
 
             _marker: PhantomData,
         }
     }
 
     /// Makes widget interactive according to the provided settings.
-    pub fn with_interactions(mut self, settings_interaction: &SettingsInteraction) -> Self {
-        self.settings_interaction = settings_interaction.clone();
+    pub fn with_interactions(self, settings_interaction: &SettingsInteraction) -> Self {
+        self.with_interactions_owned(settings_interaction.clone())
+    }
+
+    /// The same as [`GraphView::with_interactions`], but takes the settings without cloning them.
+    pub fn with_interactions_owned(mut self, settings_interaction: SettingsInteraction) -> Self {
+        self.settings_interaction = settings_interaction;
         self
     }
 
     /// Modifies default behaviour of navigation settings.
-    pub fn with_navigations(mut self, settings_navigation: &SettingsNavigation) -> Self {
-        self.settings_navigation = settings_navigation.clone();
+    pub fn with_navigations(self, settings_navigation: &SettingsNavigation) -> Self {
+        self.with_navigations_owned(settings_navigation.clone())
+    }
+
+    /// The same as [`GraphView::with_navigations`], but takes the settings without cloning them.
+    pub fn with_navigations_owned(mut self, settings_navigation: SettingsNavigation) -> Self {
+        self.settings_navigation = settings_navigation;
         self
     }
 
     /// Modifies default style settings.
-    pub fn with_styles(mut self, settings_style: &SettingsStyle) -> Self {
-        self.settings_style = settings_style.clone();
+    pub fn with_styles(self, settings_style: &SettingsStyle) -> Self {
+        self.with_styles_owned(settings_style.clone())
+    }
+
+    /// The same as [`GraphView::with_styles`], but takes the settings without cloning them,
+    /// e.g. when the style is built every frame.
+    pub fn with_styles_owned(mut self, settings_style: SettingsStyle) -> Self {
+        self.settings_style = settings_style;
+        self
+    }
+
+    /// Shows a context menu when a node is clicked with the secondary button.
+    ///
+    /// The closure is called every frame while the menu is open with the menu [`Ui`], the index of the clicked node
+    /// and the node itself. Close the menu with [`Ui::close_menu`] when an item is chosen.
+    pub fn with_node_context_menu(
+        mut self,
+        menu: impl FnMut(&mut Ui, NodeIndex<Ix>, &Node<N, E, Ty, Ix, Dn>) + 'a,
+    ) -> Self {
+        self.node_context_menu = Some(Box::new(menu));
+        self
+    }
+
+    /// Shows a context menu when empty space of the canvas is clicked with the secondary button.
+    ///
+    /// The closure is called every frame while the menu is open with the menu [`Ui`] and the clicked position
+    /// in canvas coordinates, which can be used to place new nodes.
+    pub fn with_canvas_context_menu(mut self, menu: impl FnMut(&mut Ui, Pos2) + 'a) -> Self {
+        self.canvas_context_menu = Some(Box::new(menu));
+        self
+    }
+
+    /// Shows a tooltip next to the pointer while it is over an edge, e.g. with the edge payload.
+    ///
+    /// The closure is called every frame the edge is hovered with the tooltip [`Ui`], the index of the edge
+    /// and the edge itself. Tooltips are shown only with `SettingsInteraction::with_edge_tooltips_enabled`.
+    pub fn with_edge_tooltip(
+        mut self,
+        tooltip: impl FnMut(&mut Ui, EdgeIndex<Ix>, &Edge<N, E, Ty, Ix, Dn, De>) + 'a,
+    ) -> Self {
+        self.edge_tooltip = Some(Box::new(tooltip));
+        self
+    }
+
+    /// Draws custom shapes, e.g. annotations, on top of the graph every frame.
+    ///
+    /// The closure gets the painter of the widget clipped to its rect and the current [`Metadata`] to convert
+    /// canvas coordinates of the shapes to screen coordinates, see [`Metadata::canvas_to_screen_pos`].
+    pub fn with_overlay(mut self, overlay: impl FnMut(&Painter, &Metadata) + 'a) -> Self {
+        self.overlay = Some(Box::new(overlay));
+        self
+    }
+
+    /// Colors nodes by the category of their payload using the palette.
+    ///
+    /// Categories are mapped to colors deterministically, see [`Palette::color`]. Category colors take
+    /// precedence over component colors from [`SettingsStyle`], colors set with `Node::set_color` take
+    /// precedence over both.
+    pub fn with_node_palette<K: Hash>(
+        mut self,
+        palette: Palette,
+        category: impl Fn(&N) -> K + 'a,
+    ) -> Self {
+        self.node_category_color = Some(Box::new(move |n| palette.color(&category(n))));
+        self
+    }
+
+    /// Colors nodes along the colormap by a scalar value of their payload, e.g. a centrality score.
+    ///
+    /// Values are normalized over the range of values of all nodes every frame. Value colors take precedence
+    /// over component colors from [`SettingsStyle`], category colors from [`GraphView::with_node_palette`]
+    /// and colors set with `Node::set_color` take precedence over value colors.
+    pub fn with_node_colormap(
+        mut self,
+        colormap: Colormap,
+        value: impl Fn(&N) -> f32 + 'a,
+    ) -> Self {
+        self.node_value_color = Some((colormap, Box::new(value)));
+        self
+    }
+
+    /// Multiplies opacity of every node and its incident edges by the alpha in range `[0, 1]` returned for the node,
+    /// e.g. to dim nodes not matching a search instead of removing them.
+    ///
+    /// The alpha is computed every frame and only affects drawing, faded nodes stay interactive unless
+    /// `Node::set_interactable` disables them.
+    pub fn with_node_alpha_fn(
+        mut self,
+        alpha: impl Fn(&Node<N, E, Ty, Ix, Dn>) -> f32 + 'a,
+    ) -> Self {
+        self.node_alpha = Some(Box::new(alpha));
+        self
+    }
+
+    /// Bends every edge by the curvature read from its payload, see `Edge::set_curvature`.
+    ///
+    /// The curvature is read every frame, so edges added later and payload changes are picked up.
+    /// Curvatures set with `Edge::set_curvature` are overwritten while the function is set.
+    pub fn with_edge_curvature_fn(mut self, curvature: impl Fn(&E) -> f32 + 'a) -> Self {
+        self.edge_curvature = Some(Box::new(curvature));
         self
     }
 
@@ -180,6 +403,110 @@ where
         GraphView::<N, E, Ty, Ix, Dn, De, S, L>::reset_layout(ui);
     }
 
+    /// Fits the graph to the screen once in the next frame, regardless of the fit to screen mode.
+    pub fn request_fit(ui: &mut Ui) {
+        let mut meta = Metadata::load(ui);
+        meta.fit_requested = true;
+        meta.save(ui);
+    }
+
+    /// Pans and zooms in the next frame so the nodes fit the screen, e.g. to frame search results.
+    /// A single node is framed with the same default size an empty graph is fitted with. An empty slice does nothing.
+    pub fn fit_to_nodes(ui: &mut Ui, nodes: &[NodeIndex<Ix>]) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let mut meta = Metadata::load(ui);
+        meta.fit_nodes = Some(nodes.iter().copied().map(NodeIndex::index).collect());
+        meta.save(ui);
+    }
+
+    /// Suspends layout steps and fit to screen until [`GraphView::end_batch`] is called, so bulk changes
+    /// of the graph applied over several frames do not cause intermediate layouts and camera jumps.
+    ///
+    /// The graph is still drawn and interactive while the batch is active.
+    pub fn begin_batch(ui: &mut Ui) {
+        let mut meta = Metadata::load(ui);
+        meta.batch = true;
+        meta.save(ui);
+    }
+
+    /// Resumes layout and fit to screen suspended by [`GraphView::begin_batch`]. If the graph would have been
+    /// fitted during the batch it is fitted once in the next frame.
+    pub fn end_batch(ui: &mut Ui) {
+        let mut meta = Metadata::load(ui);
+        meta.batch = false;
+        meta.save(ui);
+    }
+
+    /// Returns nodes the pointer interacted with when the widget was shown last, e.g. to react to clicks
+    /// without subscribing to events:
+    ///
+    /// ```
+    /// use egui_graphs::{DefaultGraphView, Graph};
+    ///
+    /// let mut g = Graph::<()>::default();
+    /// g.add_node(());
+    ///
+    /// let _ = egui::Context::default().run(egui::RawInput::default(), |ctx| {
+    ///     egui::CentralPanel::default().show(ctx, |ui| {
+    ///         let mut view = DefaultGraphView::new(&mut g);
+    ///         ui.add(&mut view);
+    ///         if let Some(idx) = view.graph_response().clicked_node {
+    ///             println!("clicked {idx:?}");
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    ///
+    /// Clicks are reported regardless of interaction settings.
+    pub fn graph_response(&self) -> GraphResponse<Ix> {
+        self.graph_response
+    }
+
+    /// Returns the edge under the pointer in the last frame. Nodes take precedence over edges below them.
+    pub fn hovered_edge(ui: &Ui) -> Option<EdgeIndex<Ix>> {
+        Metadata::load(ui).hovered_edge.map(EdgeIndex::new)
+    }
+
+    /// Returns the node clicked last, e.g. to open a popup at it together with the response of the widget.
+    ///
+    /// Clicks are tracked regardless of interaction settings, a click on empty space clears the node.
+    pub fn last_clicked_node(ui: &Ui) -> Option<NodeIndex<Ix>> {
+        Metadata::load(ui).last_clicked_node.map(NodeIndex::new)
+    }
+
+    /// Dims all nodes and edges except the node and its neighborhood of `hops` edges in any direction.
+    /// Dimming is configured with `SettingsStyle::with_focus_dim`.
+    pub fn focus_on(ui: &mut Ui, idx: NodeIndex<Ix>, hops: usize) {
+        let mut meta = Metadata::load(ui);
+        meta.focus = Some((idx.index(), hops));
+        meta.save(ui);
+    }
+
+    /// Keeps the node in the center of the widget by adjusting pan every frame, zoom is not changed.
+    /// Passing `None` stops following.
+    ///
+    /// Manual panning pauses or stops following depending on [`crate::SettingsNavigation::with_follow_on_pan`].
+    pub fn follow_node(ui: &mut Ui, idx: Option<NodeIndex<Ix>>) {
+        let mut meta = Metadata::load(ui);
+        meta.follow = idx.map(NodeIndex::index);
+        meta.save(ui);
+    }
+
+    /// Returns the node followed by the camera, see [`GraphView::follow_node`].
+    pub fn followed_node(ui: &Ui) -> Option<NodeIndex<Ix>> {
+        Metadata::load(ui).follow.map(NodeIndex::new)
+    }
+
+    /// Clears focus set by [`GraphView::focus_on`] restoring full brightness.
+    pub fn clear_focus(ui: &mut Ui) {
+        let mut meta = Metadata::load(ui);
+        meta.focus = None;
+        meta.save(ui);
+    }
+
     /// Resets navigation metadata
     pub fn reset_metadata(ui: &mut Ui) {
         Metadata::default().save(ui);
@@ -187,19 +514,44 @@ where
 
     /// Resets layout state
     pub fn reset_layout(ui: &mut Ui) {
+        GraphView::<N, E, Ty, Ix, Dn, De, S, L>::set_layout_state(ui, S::default());
+    }
+
+    /// Replaces layout state, e.g. to configure the layout before the first frame.
+    pub fn set_layout_state(ui: &mut Ui, state: S) {
         ui.data_mut(|data| {
-            data.insert_persisted(Id::new(KEY_LAYOUT), S::default());
+            data.insert_persisted(Id::new(KEY_LAYOUT), state);
         });
     }
 
     #[cfg(feature = "events")]
     /// Allows to supply channel where events happening in the graph will be reported.
+    ///
+    /// Events are dropped once the receiver is disconnected, so it can be dropped before the last frame.
     pub fn with_events(mut self, events_publisher: &'a Sender<Event>) -> Self {
         self.events_publisher = Some(events_publisher);
         self
     }
 
-    fn sync_layout(&mut self, ui: &mut Ui) {
+    /// Appends events happening in the graph during this frame to the provided vector.
+    ///
+    /// Events can be read synchronously after the widget is shown, without a channel, so unlike
+    /// [`GraphView::with_events`] it does not require the `events` feature. It is named after the
+    /// [`Event`] values it collects, the same ones sent to the channel. When both are set, every event
+    /// is reported to both.
+    pub fn with_event_buffer(mut self, buffer: &'a mut Vec<Event>) -> Self {
+        self.events_buffer = Some(RefCell::new(buffer));
+        self
+    }
+
+    /// Runs the layout step and returns true if any node was moved by it.
+    fn sync_layout(&mut self, ui: &mut Ui) -> bool {
+        let locations = self
+            .g
+            .nodes_iter()
+            .map(|(_, n)| n.location())
+            .collect::<Vec<_>>();
+
         ui.data_mut(|data| {
             let state = data
                 .get_persisted::<S>(Id::new(KEY_LAYOUT))
@@ -209,6 +561,8 @@ where
 
             data.insert_persisted(Id::new(KEY_LAYOUT), layout.state());
         });
+
+        !self.g.nodes_iter().map(|(_, n)| n.location()).eq(locations)
     }
 
     fn sync_state(&mut self, meta: &mut Metadata) {
@@ -216,8 +570,13 @@ where
         let mut selected_edges = Vec::new();
         let mut dragged = None;
 
+        self.g.sync_collapsed();
+
         meta.reset_bounds();
         self.g.nodes_iter().for_each(|(idx, n)| {
+            if self.g.collapsed_into(idx).is_some() {
+                return;
+            }
             if n.dragged() {
                 dragged = Some(idx);
             }
@@ -237,23 +596,260 @@ where
         self.g.set_selected_nodes(selected_nodes);
         self.g.set_selected_edges(selected_edges);
         self.g.set_dragged_node(dragged);
+
+        self.sync_computed_colors(meta);
+        self.sync_computed_alpha();
+        self.sync_edge_curvatures();
+    }
+
+    /// Keeps node sizes constant in screen points if enabled, synced whenever the zoom may have changed.
+    fn sync_node_size_scale(&mut self, meta: &Metadata) {
+        let scale = self.settings_style.node_size_scale(meta.zoom);
+        self.g.sync_node_size_scale(scale);
+    }
+
+    fn sync_edge_curvatures(&mut self) {
+        let Some(curvature) = self.edge_curvature.as_ref() else {
+            return;
+        };
+
+        self.g.g.edge_weights_mut().for_each(|e| {
+            let c = curvature(e.payload());
+            e.set_curvature(c);
+        });
+    }
+
+    fn sync_computed_alpha(&mut self) {
+        let alpha = self.node_alpha.as_ref();
+        self.g.g.node_weights_mut().for_each(|n| {
+            let a = alpha.map(|alpha| alpha(n));
+            n.set_alpha_computed(a);
+        });
+    }
+
+    /// Recomputes edge bundles if node positions, edges or bundling strength changed since the last computation.
+    fn sync_bundling(&mut self, meta: &mut Metadata) {
+        let strength = self.settings_style.bundling_strength;
+
+        // without bundling only bundles left from the previous computation are cleared
+        if strength <= 0. {
+            if meta.bundling_hash != 0 {
+                meta.bundling_hash = 0;
+                self.g.g.edge_weights_mut().for_each(|e| e.set_bundle(None));
+            }
+            return;
+        }
+
+        // only straight edges are bundled
+        let edges = self
+            .g
+            .g
+            .edge_references()
+            .filter(|e| e.weight().order() == 0)
+            .filter_map(|e| {
+                let (start, end) = self.g.visible_edge_endpoints(e.id())?;
+                if start == end {
+                    return None;
+                }
+                let start = self.g.node(start)?.location();
+                let end = self.g.node(end)?.location();
+                Some((e.id(), (start, end)))
+            })
+            .collect::<Vec<_>>();
+
+        let mut hasher = DefaultHasher::new();
+        strength.to_bits().hash(&mut hasher);
+        for (idx, (start, end)) in &edges {
+            idx.index().hash(&mut hasher);
+            [start.x, start.y, end.x, end.y]
+                .map(f32::to_bits)
+                .hash(&mut hasher);
+        }
+        let hash = hasher.finish();
+        if hash == meta.bundling_hash {
+            return;
+        }
+        meta.bundling_hash = hash;
+
+        self.g.g.edge_weights_mut().for_each(|e| e.set_bundle(None));
+
+        let segments = edges.iter().map(|(_, s)| *s).collect::<Vec<_>>();
+        for ((idx, _), b) in edges.iter().zip(bundle(&segments, strength)) {
+            self.g.edge_mut(*idx).unwrap().set_bundle(b);
+        }
+    }
+
+    /// Colors nodes by their payload category, payload value or connected component or clears computed colors
+    /// if all are disabled.
+    fn sync_computed_colors(&mut self, meta: &mut Metadata) {
+        let components_generation = meta.components_generation.take();
+
+        if let Some(color) = &self.node_category_color {
+            self.g
+                .g
+                .node_weights_mut()
+                .for_each(|n| n.set_color_computed(Some(color(n.payload()))));
+            return;
+        }
+
+        if let Some((colormap, value)) = &self.node_value_color {
+            let values = self
+                .g
+                .g
+                .node_weights()
+                .map(|n| value(n.payload()))
+                .collect::<Vec<_>>();
+            for (n, v) in self.g.g.node_weights_mut().zip(normalize(&values)) {
+                n.set_color_computed(Some(colormap.color(v)));
+            }
+            return;
+        }
+
+        if !self.settings_style.color_by_component {
+            self.g
+                .g
+                .node_weights_mut()
+                .for_each(|n| n.set_color_computed(None));
+            return;
+        }
+
+        // components change only with the structure of the graph
+        meta.components_generation = Some(self.g.generation());
+        if components_generation == meta.components_generation {
+            return;
+        }
+
+        for (ordinal, component) in self.g.connected_components().into_iter().enumerate() {
+            for idx in component {
+                if let Some(n) = self.g.node_mut(idx) {
+                    n.set_color_computed(Some(component_color(ordinal)));
+                }
+            }
+        }
+    }
+
+    /// Advances node fades removing nodes which faded out for removal.
+    /// Returns true if any fade is still running.
+    fn handle_fades(&mut self, ui: &Ui) -> bool {
+        let duration = self.settings_style.fade_duration;
+        let step = if duration > 0. {
+            ui.input(|i| i.stable_dt) / duration
+        } else {
+            1.
+        };
+
+        self.step_fades(step)
+    }
+
+    /// Moves node opacities towards fade targets by the step deleting nodes which faded out for removal.
+    /// Returns true if any fade is still running.
+    fn step_fades(&mut self, step: f32) -> bool {
+        let mut fading = false;
+        let mut removed = vec![];
+        self.g.g.node_weights_mut().for_each(|n| {
+            if n.step_fade(step) {
+                removed.push(n.id());
+            }
+            fading |= n.fading();
+        });
+
+        for idx in removed {
+            self.delete_node(idx);
+        }
+
+        fading
+    }
+
+    /// Fits the graph to the screen according to the fit to screen mode or if the fit was requested.
+    /// Recenters or refits the graph when the widget size changes noticeably.
+    fn handle_resize(&self, r: &Response, meta: &mut Metadata) {
+        let (prev, size) = (meta.canvas_size, r.rect.size());
+        let change = (size - prev).abs() / prev.max(Vec2::splat(1.));
+        if change.max_elem() < RESIZE_THRESHOLD {
+            return;
+        }
+        meta.canvas_size = size;
+        if meta.first_frame {
+            return;
+        }
+
+        match self.settings_navigation.on_resize {
+            OnResize::Keep => {}
+            OnResize::Recenter => self.set_pan(meta.pan + (size - prev) / 2., meta),
+            OnResize::Fit => meta.fit_requested = true,
+        }
     }
 
-    /// Fits the graph to the screen if it is the first frame or
-    /// fit to screen setting is enabled;
     fn handle_fit_to_screen(&self, r: &Response, meta: &mut Metadata) {
-        if !meta.first_frame && !self.settings_navigation.fit_to_screen_enabled {
+        let fit = match self.settings_navigation.fit_to_screen {
+            FitToScreen::Continuous => true,
+            FitToScreen::Once => meta.first_frame || meta.fit_requested,
+            FitToScreen::Off => meta.fit_requested,
+        };
+        if meta.batch {
+            // keep the fit pending until the batch ends
+            meta.fit_requested = fit;
+            return;
+        }
+
+        if let Some(nodes) = meta.fit_nodes.take() {
+            if let Some(bounds) = self.nodes_bounds(&nodes) {
+                self.fit_to_bounds(&r.rect, bounds, meta);
+                self.set_fitted(meta);
+                return;
+            }
+        }
+
+        meta.fit_requested = false;
+        if !fit {
             return;
         }
 
+        let (zoom, pan) = (meta.zoom, meta.pan);
         self.fit_to_screen(&r.rect, meta);
+        if meta.zoom != zoom || meta.pan != pan {
+            self.set_fitted(meta);
+        }
     }
 
-    fn handle_click(&mut self, resp: &Response, meta: &mut Metadata) {
-        if !resp.clicked() && !resp.double_clicked() {
+    /// Handles clicks of the widget and node drags released below the drag threshold at `drag_click`.
+    fn handle_click(&mut self, resp: &Response, drag_click: Option<Pos2>, meta: &mut Metadata) {
+        let disambiguate = self.settings_interaction.click_disambiguation;
+        let now = resp.ctx.input(|i| i.time);
+        let delay = resp.ctx.options(|o| o.input_options.max_double_click_delay);
+
+        if let Some((pos, time, modifiers)) = meta.pending_click {
+            if !disambiguate || resp.double_clicked() {
+                // second click of double click cancels the deferred single click
+                meta.pending_click = None;
+            } else if now - time > delay && !resp.ctx.input(|i| i.pointer.any_down()) {
+                meta.pending_click = None;
+                self.click(meta.canvas_to_screen_pos(pos), false, modifiers, meta);
+            } else {
+                resp.ctx
+                    .request_repaint_after(Duration::from_secs_f64((delay - (now - time)).max(0.)));
+            }
+        }
+
+        if !resp.clicked() && !resp.double_clicked() && drag_click.is_none() {
             return;
         }
 
+        let Some(cursor_pos) = drag_click.or(resp.hover_pos()) else {
+            return;
+        };
+        let modifiers = resp.ctx.input(|i| i.modifiers);
+        if disambiguate && !resp.double_clicked() {
+            meta.pending_click = Some((meta.screen_to_canvas_pos(cursor_pos), now, modifiers));
+            resp.ctx
+                .request_repaint_after(Duration::from_secs_f64(delay));
+            return;
+        }
+
+        self.click(cursor_pos, resp.double_clicked(), modifiers, meta);
+    }
+
+    fn click(&mut self, cursor_pos: Pos2, double: bool, modifiers: Modifiers, meta: &mut Metadata) {
         let clickable = self.settings_interaction.node_clicking_enabled
             || self.settings_interaction.node_selection_enabled
             || self.settings_interaction.node_selection_multi_enabled
@@ -261,17 +857,38 @@ where
             || self.settings_interaction.edge_selection_enabled
             || self.settings_interaction.edge_selection_multi_enabled;
 
+        let found_edge = self.g.edge_by_screen_pos_with_padding(
+            meta,
+            cursor_pos,
+            self.settings_interaction.edge_hit_padding,
+        );
+        let found_node = self.interactable_node_at(meta, cursor_pos);
+
+        if let Some(idx) = found_node {
+            meta.last_clicked_node = Some(idx.index());
+            if double {
+                self.graph_response.double_clicked_node = Some(idx);
+            } else {
+                self.graph_response.clicked_node = Some(idx);
+            }
+        }
+
+        if found_node.is_none() && found_edge.is_none() {
+            // click on empty space is reported regardless of interaction settings
+            meta.last_clicked_node = None;
+            let pos = meta.screen_to_canvas_pos(cursor_pos);
+            if double {
+                self.set_canvas_double_clicked(pos);
+            } else {
+                self.set_canvas_clicked(pos);
+            }
+        }
+
         if !(clickable) {
             return;
         }
 
-        let Some(cursor_pos) = resp.hover_pos() else {
-            return;
-        };
-        let found_edge = self.g.edge_by_screen_pos(meta, cursor_pos);
-        let found_node = self.g.node_by_screen_pos(meta, cursor_pos);
         if found_node.is_none() && found_edge.is_none() {
-            // click on empty space
             let nodes_selectable = self.settings_interaction.node_selection_enabled
                 || self.settings_interaction.node_selection_multi_enabled;
             if nodes_selectable {
@@ -287,23 +904,335 @@ where
         }
 
         if let Some(idx) = found_node {
-            // first click of double click is handled by the lib as single click
-            // so if you double click a node it will handle it as single click at first
-            // and only after as double click
-            if resp.double_clicked() {
+            // unless click disambiguation is enabled, first click of double click is handled
+            // by the lib as single click so if you double click a node it will handle it
+            // as single click at first and only after as double click
+            if double {
                 self.handle_node_double_click(idx);
                 return;
             }
-            self.handle_node_click(idx);
+            self.handle_node_click(idx, modifiers, meta);
             return;
         }
 
         if let Some(edge_idx) = found_edge {
-            self.handle_edge_click(edge_idx);
+            if double {
+                self.handle_edge_double_click(edge_idx);
+                return;
+            }
+            self.handle_edge_click(edge_idx, modifiers);
+        }
+    }
+
+    /// Marks the node or the edge under the pointer as hovered after the hover delay.
+    /// Nodes take precedence over edges below them.
+    fn handle_hover(&mut self, resp: &Response, meta: &mut Metadata) {
+        let hovered_node = resp
+            .hover_pos()
+            .and_then(|pos| self.interactable_node_at(meta, pos));
+        self.set_cursor(resp, hovered_node.is_some());
+
+        let hovered_edge = resp.hover_pos().and_then(|pos| {
+            if hovered_node.is_some() {
+                return None;
+            }
+
+            let padding = self.settings_interaction.edge_hit_padding;
+            self.g.edge_by_screen_pos_with_padding(meta, pos, padding)
+        });
+        let (hovered_node, hovered) = self.delay_hover(resp, meta, hovered_node, hovered_edge);
+        meta.hovered_edge = hovered.map(EdgeIndex::index);
+        self.graph_response.hovered_node = hovered_node;
+
+        let prev_node = self
+            .g
+            .nodes_iter()
+            .find(|(_, n)| n.hovered())
+            .map(|(idx, _)| idx);
+        if prev_node != hovered_node {
+            if let Some(n) = prev_node.and_then(|idx| self.g.node_mut(idx)) {
+                n.set_hovered(false);
+            }
+            if let Some(n) = hovered_node.and_then(|idx| self.g.node_mut(idx)) {
+                n.set_hovered(true);
+            }
+        }
+
+        let prev = self
+            .g
+            .edges_iter()
+            .find(|(_, e)| e.hovered())
+            .map(|(idx, _)| idx);
+        if prev == hovered {
+            return;
+        }
+
+        if let Some(e) = prev.and_then(|idx| self.g.edge_mut(idx)) {
+            e.set_hovered(false);
+        }
+        if let Some(idx) = hovered {
+            self.set_edge_hovered(idx);
+        }
+    }
+
+    fn show_edge_tooltip(&mut self, ui: &Ui, resp: &Response, meta: &Metadata) {
+        if !self.settings_interaction.edge_tooltips_enabled {
+            return;
+        }
+        let (Some(tooltip), Some(idx)) = (
+            self.edge_tooltip.as_mut(),
+            meta.hovered_edge.map(EdgeIndex::new),
+        ) else {
+            return;
+        };
+        let Some(e) = self.g.edge(idx) else {
+            return;
+        };
+
+        egui::show_tooltip_at_pointer(
+            ui.ctx(),
+            ui.layer_id(),
+            resp.id.with("edge_tooltip"),
+            |ui| {
+                tooltip(ui, idx, e);
+            },
+        );
+    }
+
+    /// Passes elements under the pointer through once the pointer rested over them for the hover delay,
+    /// until then nothing is hovered.
+    fn delay_hover(
+        &self,
+        resp: &Response,
+        meta: &mut Metadata,
+        node: Option<NodeIndex<Ix>>,
+        edge: Option<EdgeIndex<Ix>>,
+    ) -> (Option<NodeIndex<Ix>>, Option<EdgeIndex<Ix>>) {
+        let now = resp.ctx.input(|i| i.time);
+        let (node_id, edge_id) = (node.map(NodeIndex::index), edge.map(EdgeIndex::index));
+        let start = match meta.hover_start {
+            Some((prev_node, prev_edge, start)) if (prev_node, prev_edge) == (node_id, edge_id) => {
+                start
+            }
+            _ => {
+                meta.hover_start = Some((node_id, edge_id, now));
+                now
+            }
+        };
+
+        let remaining = self.settings_interaction.hover_delay.as_secs_f64() - (now - start);
+        if remaining > 0. {
+            resp.ctx
+                .request_repaint_after(Duration::from_secs_f64(remaining));
+            return (None, None);
+        }
+
+        (node, edge)
+    }
+
+    /// Finds the node under the position skipping non-interactable nodes, which let interactions through.
+    fn interactable_node_at(&self, meta: &Metadata, pos: Pos2) -> Option<NodeIndex<Ix>> {
+        let padding = self.settings_interaction.node_hit_padding;
+        self.g
+            .interactable_node_by_screen_pos_with_padding(meta, pos, padding)
+    }
+
+    fn node_interactable(&self, idx: NodeIndex<Ix>) -> bool {
+        self.g.node(idx).is_some_and(Node::interactable)
+    }
+
+    /// Signals interactivity of the node under the pointer with the cursor icon.
+    fn set_cursor(&self, resp: &Response, node_hovered: bool) {
+        let settings = &self.settings_interaction;
+        let icon = if self.g.dragged_node().is_some() {
+            CursorIcon::Grabbing
+        } else if node_hovered && settings.dragging_enabled {
+            CursorIcon::Grab
+        } else if node_hovered
+            && (settings.node_clicking_enabled || settings.node_selection_enabled)
+        {
+            CursorIcon::PointingHand
+        } else {
+            return;
+        };
+
+        resp.ctx.set_cursor_icon(icon);
+    }
+
+    /// Takes keyboard focus on click if keyboard navigation or node deletion is enabled.
+    fn handle_focus(&self, resp: &Response) {
+        let settings = &self.settings_interaction;
+        if resp.clicked()
+            && (settings.keyboard_navigation_enabled || settings.node_deletion_enabled)
+        {
+            resp.request_focus();
+        }
+    }
+
+    /// Moves keyboard focus between nodes and clicks the focused node.
+    fn handle_keyboard(&mut self, ui: &Ui, resp: &Response, meta: &mut Metadata) {
+        if !self.settings_interaction.keyboard_navigation_enabled {
+            meta.focused_node = None;
+            return;
+        }
+
+        // forget focus on removed or hidden nodes
+        let visible = |g: &Graph<N, E, Ty, Ix, Dn, De>, idx: NodeIndex<Ix>| {
+            g.node(idx).is_some() && g.collapsed_into(idx).is_none()
+        };
+        if let Some(idx) = meta.focused_node {
+            if !visible(self.g, NodeIndex::new(idx)) {
+                meta.focused_node = None;
+            }
+        }
+
+        if !resp.has_focus() {
+            return;
+        }
+        ui.memory_mut(|m| {
+            m.set_focus_lock_filter(
+                resp.id,
+                EventFilter {
+                    tab: true,
+                    horizontal_arrows: true,
+                    vertical_arrows: true,
+                    escape: false,
+                },
+            );
+        });
+
+        let (forward, backward, activate, modifiers) = ui.input(|i| {
+            let tab = i.key_pressed(Key::Tab);
+            (
+                (tab && !i.modifiers.shift)
+                    || i.key_pressed(Key::ArrowRight)
+                    || i.key_pressed(Key::ArrowDown),
+                (tab && i.modifiers.shift)
+                    || i.key_pressed(Key::ArrowLeft)
+                    || i.key_pressed(Key::ArrowUp),
+                i.key_pressed(Key::Enter) || i.key_pressed(Key::Space),
+                i.modifiers,
+            )
+        });
+
+        if forward != backward {
+            let nodes = self
+                .g
+                .g
+                .node_indices()
+                .filter(|idx| visible(self.g, *idx))
+                .collect::<Vec<_>>();
+            if !nodes.is_empty() {
+                let curr = meta
+                    .focused_node
+                    .and_then(|focused| nodes.iter().position(|idx| idx.index() == focused));
+                let next = match (curr, forward) {
+                    (None, true) => 0,
+                    (None, false) => nodes.len() - 1,
+                    (Some(pos), true) => (pos + 1) % nodes.len(),
+                    (Some(pos), false) => (pos + nodes.len() - 1) % nodes.len(),
+                };
+                meta.focused_node = Some(nodes[next].index());
+            }
+        }
+
+        if activate {
+            if let Some(idx) = meta.focused_node.map(NodeIndex::new) {
+                if self.node_interactable(idx) {
+                    self.handle_node_click(idx, modifiers, meta);
+                }
+            }
+        }
+    }
+
+    /// Removes selected nodes and their edges when a delete key is pressed.
+    fn handle_node_deletion(&mut self, ui: &Ui, resp: &Response) {
+        if !self.settings_interaction.node_deletion_enabled {
+            return;
+        }
+
+        let focused = resp.has_focus() || (resp.hovered() && ui.memory(|m| m.focused().is_none()));
+        if !focused || !ui.input(|i| i.key_pressed(Key::Delete) || i.key_pressed(Key::Backspace)) {
+            return;
+        }
+
+        self.delete_selected_nodes();
+    }
+
+    /// Removes selected interactable nodes with their edges and clears the node selection.
+    fn delete_selected_nodes(&mut self) {
+        let deleted = self
+            .g
+            .selected_nodes()
+            .iter()
+            .copied()
+            .filter(|idx| self.node_interactable(*idx))
+            .collect::<Vec<_>>();
+        if deleted.is_empty() {
+            return;
+        }
+
+        // non-interactable nodes are kept in the graph, but the whole selection is cleared
+        self.deselect_all_nodes();
+        for idx in deleted {
+            self.delete_node(idx);
+        }
+        self.g.set_selected_edges(
+            self.g
+                .selected_edges()
+                .iter()
+                .copied()
+                .filter(|idx| self.g.edge(*idx).is_some())
+                .collect(),
+        );
+        if self
+            .g
+            .dragged_node()
+            .is_some_and(|idx| self.g.node(idx).is_none())
+        {
+            self.g.set_dragged_node(None);
+        }
+    }
+
+    /// Remembers the target of the secondary click and shows the context menu of the node or the canvas.
+    fn handle_context_menu(&mut self, resp: &Response, meta: &mut Metadata) {
+        if self.node_context_menu.is_none() && self.canvas_context_menu.is_none() {
+            return;
+        }
+
+        if resp.secondary_clicked() {
+            if let Some(cursor_pos) = resp.interact_pointer_pos() {
+                let node = self.interactable_node_at(meta, cursor_pos);
+                meta.context_menu = (
+                    node.map(NodeIndex::index),
+                    meta.screen_to_canvas_pos(cursor_pos),
+                );
+            }
+        }
+
+        match meta.context_menu {
+            (Some(idx), _) => {
+                let idx = NodeIndex::new(idx);
+                let (Some(menu), Some(n)) = (self.node_context_menu.as_mut(), self.g.node(idx))
+                else {
+                    return;
+                };
+                resp.context_menu(|ui| menu(ui, idx, n));
+            }
+            (None, pos) => {
+                let Some(menu) = self.canvas_context_menu.as_mut() else {
+                    return;
+                };
+                resp.context_menu(|ui| menu(ui, pos));
+            }
         }
     }
 
     fn handle_node_double_click(&mut self, idx: NodeIndex<Ix>) {
+        if self.g.node(idx).is_some_and(Node::collapsed) {
+            self.g.expand(idx);
+        }
+
         if !self.settings_interaction.node_clicking_enabled {
             return;
         }
@@ -313,7 +1242,7 @@ where
         }
     }
 
-    fn handle_node_click(&mut self, idx: NodeIndex<Ix>) {
+    fn handle_node_click(&mut self, idx: NodeIndex<Ix>, modifiers: Modifiers, meta: &mut Metadata) {
         if !self.settings_interaction.node_clicking_enabled
             && !self.settings_interaction.node_selection_enabled
         {
@@ -328,20 +1257,49 @@ where
             return;
         }
 
+        let range = modifier_held(
+            self.settings_interaction.selection_range_modifier,
+            modifiers,
+        );
+        if let (true, Some(anchor)) = (range, meta.selection_anchor) {
+            let path = self.g.shortest_path(NodeIndex::new(anchor), idx);
+            let path = if path.is_empty() { vec![idx] } else { path };
+            for n in path {
+                if self.node_interactable(n) && !self.g.node(n).unwrap().selected() {
+                    self.select_node(n);
+                }
+            }
+            return;
+        }
+        meta.selection_anchor = Some(idx.index());
+
+        let toggle = self.settings_interaction.node_selection_multi_enabled
+            || modifier_held(
+                self.settings_interaction.selection_multi_modifier,
+                modifiers,
+            );
+        let replace = self.settings_interaction.selection_multi_modifier.is_some() && !toggle;
+
         let n = self.g.node(idx).unwrap();
-        if n.selected() {
+        if n.selected() && !replace {
             self.deselect_node(idx);
             return;
         }
 
-        if !self.settings_interaction.node_selection_multi_enabled {
+        if !toggle {
             self.deselect_all();
         }
 
         self.select_node(idx);
     }
 
-    fn handle_edge_click(&mut self, idx: EdgeIndex<Ix>) {
+    fn handle_edge_double_click(&mut self, idx: EdgeIndex<Ix>) {
+        if self.settings_interaction.edge_clicking_enabled {
+            self.set_edge_double_clicked(idx);
+        }
+    }
+
+    fn handle_edge_click(&mut self, idx: EdgeIndex<Ix>, modifiers: Modifiers) {
         if !self.settings_interaction.edge_clicking_enabled
             && !self.settings_interaction.edge_selection_enabled
         {
@@ -356,88 +1314,194 @@ where
             return;
         }
 
+        let toggle = self.settings_interaction.edge_selection_multi_enabled
+            || modifier_held(
+                self.settings_interaction.selection_multi_modifier,
+                modifiers,
+            );
+        let replace = self.settings_interaction.selection_multi_modifier.is_some() && !toggle;
+
         let e = self.g.edge(idx).unwrap();
-        if e.selected() {
+        if e.selected() && !replace {
             self.deselect_edge(idx);
             return;
         }
 
-        if !self.settings_interaction.edge_selection_multi_enabled {
+        if !toggle {
             self.deselect_all();
         }
 
         self.select_edge(idx);
     }
 
-    fn handle_node_drag(&mut self, resp: &Response, meta: &mut Metadata) {
-        if !self.settings_interaction.dragging_enabled {
+    fn handle_edge_creation(&mut self, ui: &Ui, resp: &Response, meta: &mut Metadata) {
+        // created edges are only reported with events, without them the gesture would do nothing
+        if !cfg!(feature = "events") || !self.settings_interaction.edge_creation_enabled {
+            return;
+        }
+
+        let modifier = self.settings_interaction.edge_creation_modifier;
+        if resp.drag_started_by(PointerButton::Primary)
+            && ui.input(|i| i.modifiers.contains(modifier))
+        {
+            if let Some(pos) = resp.hover_pos() {
+                if let Some(idx) = self.interactable_node_at(meta, pos) {
+                    meta.edge_creation = Some((idx.index(), meta.screen_to_canvas_pos(pos)));
+                }
+            }
+        }
+
+        let Some((start, _)) = meta.edge_creation else {
             return;
+        };
+
+        if let Some(pos) = resp.hover_pos() {
+            meta.edge_creation = Some((start, meta.screen_to_canvas_pos(pos)));
+        }
+        // the preview snaps to the same node the edge is created with, empty space and the start node cancel
+        meta.edge_creation_end = resp
+            .hover_pos()
+            .and_then(|pos| self.interactable_node_at(meta, pos))
+            .map(NodeIndex::index)
+            .filter(|end| *end != start);
+
+        if !resp.drag_stopped() {
+            return;
+        }
+
+        meta.edge_creation = None;
+        if let Some(end) = meta.edge_creation_end.take() {
+            self.set_edge_created(NodeIndex::new(start), NodeIndex::new(end));
+        }
+    }
+
+    /// Moves the dragged node. Returns the position in screen points where a node was pressed
+    /// if the gesture ended below the drag threshold, such a gesture counts as a click.
+    fn handle_node_drag(&mut self, resp: &Response, meta: &mut Metadata) -> Option<Pos2> {
+        if !self.settings_interaction.dragging_enabled {
+            return None;
+        }
+
+        if meta.edge_creation.is_some() {
+            return None;
         }
 
         if !resp.dragged_by(PointerButton::Primary)
             && !resp.drag_started_by(PointerButton::Primary)
             && !resp.drag_stopped_by(PointerButton::Primary)
         {
-            return;
+            return None;
         }
 
         if resp.drag_started() {
-            if let Some(idx) = self.g.node_by_screen_pos(meta, resp.hover_pos().unwrap()) {
-                self.set_drag_start(idx);
+            if let Some(idx) = self.interactable_node_at(meta, resp.hover_pos().unwrap()) {
+                meta.drag_pending = Some((idx.index(), Vec2::ZERO));
+            }
+        }
+
+        // the node stays in place until the pointer moves farther than the drag threshold
+        if let Some((idx, moved)) = meta.drag_pending.take() {
+            let moved = moved + resp.drag_delta();
+            let threshold = self.settings_interaction.drag_threshold;
+            if threshold <= 0. || moved.length() > threshold {
+                self.set_drag_start(NodeIndex::new(idx), meta);
+            } else if resp.drag_stopped() {
+                return resp.hover_pos().map(|pos| pos - moved);
+            } else {
+                meta.drag_pending = Some((idx, moved));
             }
         }
 
         // handle mouse drag
+        // with limited drag step the node only follows the pointer below
         if resp.dragged()
+            && self.settings_interaction.max_drag_step.is_none()
             && self.g.dragged_node().is_some()
             && (resp.drag_delta().x.abs() > 0. || resp.drag_delta().y.abs() > 0.)
         {
             let n_idx_dragged = self.g.dragged_node().unwrap();
-            let delta_in_graph_coords = resp.drag_delta() / meta.zoom;
-            self.move_node(n_idx_dragged, delta_in_graph_coords);
+            let delta_in_graph_coords = meta.screen_to_canvas_vec(resp.drag_delta());
+            let delta = self.lock_axis(resp, n_idx_dragged, delta_in_graph_coords, meta);
+            self.move_node(n_idx_dragged, delta);
         }
 
         // compensate movement of the node which is not caused by dragging
         if let Some(n_idx_dragged) = self.g.dragged_node() {
             if let Some(mouse_pos) = resp.hover_pos() {
                 if let Some(node) = self.g.node(n_idx_dragged) {
-                    let node_pos = node.location() * meta.zoom + meta.pan;
+                    let node_pos = meta.canvas_to_screen_pos(node.location());
                     let delta = mouse_pos - node_pos;
 
-                    self.move_node(n_idx_dragged, delta / meta.zoom);
+                    let delta = self
+                        .settings_interaction
+                        .clamp_drag_step(meta.screen_to_canvas_vec(delta));
+                    let delta = self.lock_axis(resp, n_idx_dragged, delta, meta);
+                    self.move_node(n_idx_dragged, delta);
                 }
             }
         }
 
         if resp.drag_stopped() && self.g.dragged_node().is_some() {
             let n_idx = self.g.dragged_node().unwrap();
-            self.set_drag_end(n_idx);
+            self.set_drag_end(n_idx, meta);
         }
+
+        None
+    }
+
+    /// Adjusts movement of the dragged node so that it stays on the dominant axis of the movement
+    /// since the drag started while the axis lock modifier is held.
+    fn lock_axis(&self, resp: &Response, idx: NodeIndex<Ix>, delta: Vec2, meta: &Metadata) -> Vec2 {
+        let modifiers = resp.ctx.input(|i| i.modifiers);
+        if !modifier_held(self.settings_interaction.axis_lock_modifier, modifiers) {
+            return delta;
+        }
+
+        let (Some(start), Some(n)) = (meta.drag_start, self.g.node(idx)) else {
+            return delta;
+        };
+
+        lock_to_axis(start, n.location(), delta)
     }
 
     fn fit_to_screen(&self, rect: &Rect, meta: &mut Metadata) {
-        // calculate graph dimensions with decorative padding
         let bounds = meta.graph_bounds();
+        self.fit_to_bounds(rect, bounds, meta);
+    }
+
+    /// Returns bounds of the existing nodes among the indices, `None` if there are none.
+    fn nodes_bounds(&self, nodes: &[usize]) -> Option<Rect> {
+        let found = nodes
+            .iter()
+            .filter_map(|idx| self.g.node(NodeIndex::new(*idx)))
+            .collect::<Vec<_>>();
+
+        match found.as_slice() {
+            [] => None,
+            [n] => Some(Rect::from_center_size(n.location(), DEFAULT_FIT_SIZE)),
+            found => {
+                let mut bounds = Metadata::default();
+                for n in found {
+                    bounds.comp_iter_bounds(n);
+                }
+                Some(bounds.graph_bounds())
+            }
+        }
+    }
+
+    fn fit_to_bounds(&self, rect: &Rect, bounds: Rect, meta: &mut Metadata) {
+        // calculate graph dimensions
         let mut diag = bounds.max - bounds.min;
 
         // if the graph is empty or consists from one node, use a default size
         if diag == Vec2::ZERO {
-            diag = Vec2::new(1., 100.);
+            diag = DEFAULT_FIT_SIZE;
         }
 
-        let graph_size = diag * (1. + self.settings_navigation.screen_padding);
-        let (width, height) = (graph_size.x, graph_size.y);
-
-        // calculate canvas dimensions
-        let canvas_size = rect.size();
-        let (canvas_width, canvas_height) = (canvas_size.x, canvas_size.y);
-
-        // calculate zoom factors for x and y to fit the graph inside the canvas
-        let zoom_x = canvas_width / width;
-        let zoom_y = canvas_height / height;
-
-        // choose the minimum of the two zoom factors to avoid distortion
-        let new_zoom = zoom_x.min(zoom_y);
+        let new_zoom = self
+            .settings_navigation
+            .screen_padding
+            .fit_zoom(diag, rect.size());
 
         // calculate the zoom delta and call handle_zoom to adjust the zoom factor
         let zoom_delta = new_zoom / meta.zoom - 1.0;
@@ -451,14 +1515,135 @@ where
         self.set_pan(new_pan, meta);
     }
 
-    fn handle_navigation(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) {
+    /// Returns `true` if the graph was panned manually.
+    fn handle_navigation(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) -> bool {
         if !meta.first_frame {
             meta.pan += resp.rect.left_top() - meta.top_left;
         }
         meta.top_left = resp.rect.left_top();
 
+        if self.handle_overview(ui, resp, meta) {
+            return false;
+        }
+        if self.handle_touch(ui, resp, meta) {
+            return true;
+        }
+
         self.handle_zoom(ui, resp, meta);
-        self.handle_pan(resp, meta);
+        let dragged = self.handle_pan(resp, meta);
+        let scrolled = self.handle_scroll_pan(ui, resp, meta);
+        dragged || scrolled
+    }
+
+    /// Pans the graph so that the followed node stays in the center of the widget.
+    fn handle_follow(&self, resp: &Response, panned: bool, meta: &mut Metadata) {
+        let Some(idx) = meta.follow else {
+            return;
+        };
+
+        if panned {
+            if self.settings_navigation.follow_on_pan == FollowOnPan::Stop {
+                meta.follow = None;
+            }
+            return;
+        }
+
+        // the overview restores the camera on release
+        if meta.overview.is_some() {
+            return;
+        }
+
+        let Some(n) = self.g.node(NodeIndex::new(idx)) else {
+            meta.follow = None;
+            return;
+        };
+
+        let new_pan = resp.rect.center().to_vec2() - n.location().to_vec2() * meta.zoom;
+        if new_pan != meta.pan {
+            self.set_pan(new_pan, meta);
+        }
+    }
+
+    /// Fits the graph to the screen while the overview key is held and restores the camera on release.
+    /// Returns true if the overview is shown.
+    fn handle_overview(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) -> bool {
+        let held = self
+            .settings_navigation
+            .overview_key
+            .is_some_and(|key| ui.input(|i| i.key_down(key)));
+
+        match (held, meta.overview) {
+            (true, None) if resp.hovered() || resp.has_focus() => {
+                meta.overview = Some((meta.zoom, meta.pan));
+                self.fit_to_screen(&resp.rect, meta);
+                true
+            }
+            (true, Some(_)) => true,
+            (false, Some((zoom, pan))) => {
+                meta.overview = None;
+                self.set_zoom(zoom, meta);
+                self.set_pan(pan, meta);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_scroll_pan(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) -> bool {
+        if !self.settings_navigation.zoom_and_pan_enabled
+            || !self.settings_navigation.scroll_to_pan
+            || !resp.hovered()
+        {
+            return false;
+        }
+
+        // scrolling with zoom modifiers is already handled as zoom
+        let delta = ui.input(|i| {
+            if i.modifiers.ctrl || i.modifiers.command || i.modifiers.mac_cmd {
+                Vec2::ZERO
+            } else {
+                scroll_pan_delta(i.raw_scroll_delta, i.modifiers)
+            }
+        });
+        if delta == Vec2::ZERO {
+            return false;
+        }
+
+        let new_pan = meta.pan + delta * self.settings_navigation.scroll_pan_factor;
+        self.set_pan(new_pan, meta);
+        true
+    }
+
+    /// Pans with two-finger drag and zooms with pinch around the gesture center.
+    /// Returns `true` if a multi-touch gesture is in progress and other navigation should be skipped.
+    fn handle_touch(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) -> bool {
+        let touch = if self.settings_navigation.touch_enabled {
+            ui.input(egui::InputState::multi_touch)
+        } else {
+            None
+        };
+        let Some(touch) = touch.filter(|t| resp.rect.contains(t.start_pos)) else {
+            meta.touch = None;
+            return false;
+        };
+
+        // egui reports only the start position of the gesture, the center is tracked by its movement
+        let center = match meta.touch {
+            Some((start_time, center)) if start_time == touch.start_time => {
+                center + touch.translation_delta
+            }
+            _ => touch.start_pos,
+        };
+        meta.touch = Some((touch.start_time, center));
+
+        if touch.translation_delta != Vec2::ZERO {
+            self.set_pan(meta.pan + touch.translation_delta, meta);
+        }
+        if touch.zoom_delta != 1. {
+            self.zoom(&resp.rect, touch.zoom_delta - 1., Some(center), meta);
+        }
+
+        true
     }
 
     fn handle_zoom(&self, ui: &Ui, resp: &Response, meta: &mut Metadata) {
@@ -472,29 +1657,40 @@ where
                 return;
             }
 
-            let step = self.settings_navigation.zoom_speed * (delta - 1.).signum();
+            let step = self.settings_navigation.zoom_step(delta);
             self.zoom(&resp.rect, step, i.pointer.hover_pos(), meta);
         });
     }
 
-    fn handle_pan(&self, resp: &Response, meta: &mut Metadata) {
+    fn handle_pan(&self, resp: &Response, meta: &mut Metadata) -> bool {
         if !self.settings_navigation.zoom_and_pan_enabled {
-            return;
+            return false;
         }
 
         if (resp.dragged_by(PointerButton::Middle) || resp.dragged_by(PointerButton::Primary))
-            && self.g.dragged_node().is_none()
+            && !self.drag_captured(meta)
             && (resp.drag_delta().x.abs() > 0. || resp.drag_delta().y.abs() > 0.)
         {
             let new_pan = meta.pan + resp.drag_delta();
             self.set_pan(new_pan, meta);
+            return true;
         }
+
+        false
+    }
+
+    /// Whether the pointer drag belongs to a node or a new edge and should not pan the canvas,
+    /// including presses on a node which have not passed the drag threshold yet.
+    fn drag_captured(&self, meta: &Metadata) -> bool {
+        self.g.dragged_node().is_some()
+            || meta.edge_creation.is_some()
+            || meta.drag_pending.is_some()
     }
 
     /// Zooms the graph by the given delta. It also compensates with pan to keep the zoom center in the same place.
     fn zoom(&self, rect: &Rect, delta: f32, zoom_center: Option<Pos2>, meta: &mut Metadata) {
-        let center_pos = zoom_center.unwrap_or(rect.center()).to_vec2();
-        let graph_center_pos = (center_pos - meta.pan) / meta.zoom;
+        let center_pos = zoom_center.unwrap_or(rect.center());
+        let graph_center_pos = meta.screen_to_canvas_pos(center_pos).to_vec2();
         let factor = 1. + delta;
         let new_zoom = meta.zoom * factor;
 
@@ -509,7 +1705,6 @@ where
         let n = self.g.node_mut(idx).unwrap();
         n.set_selected(true);
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeSelect(PayloadNodeSelect { id: idx.index() }));
     }
 
@@ -517,35 +1712,79 @@ where
         let n = self.g.node_mut(idx).unwrap();
         n.set_selected(false);
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeDeselect(PayloadNodeDeselect { id: idx.index() }));
     }
 
     #[allow(unused_variables, clippy::unused_self)]
     fn set_node_clicked(&self, idx: NodeIndex<Ix>) {
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeClick(PayloadNodeClick { id: idx.index() }));
     }
 
     #[allow(unused_variables, clippy::unused_self)]
     fn set_node_double_clicked(&self, idx: NodeIndex<Ix>) {
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeDoubleClick(PayloadNodeDoubleClick {
             id: idx.index(),
         }));
     }
 
+    #[allow(clippy::unused_self)]
+    fn set_ready(&self) {
+        self.publish_event(Event::Ready);
+    }
+
+    #[allow(unused_variables, clippy::unused_self)]
+    fn set_fitted(&self, meta: &Metadata) {
+        self.publish_event(Event::FitToScreen(PayloadFitToScreen {
+            new_zoom: meta.zoom,
+            new_pan: meta.pan.into(),
+        }));
+    }
+
+    #[allow(unused_variables, clippy::unused_self)]
+    fn set_canvas_clicked(&self, pos: Pos2) {
+        self.publish_event(Event::CanvasClick(PayloadCanvasClick {
+            pos: [pos.x, pos.y],
+        }));
+    }
+
+    #[allow(unused_variables, clippy::unused_self)]
+    fn set_canvas_double_clicked(&self, pos: Pos2) {
+        self.publish_event(Event::CanvasDoubleClick(PayloadCanvasDoubleClick {
+            pos: [pos.x, pos.y],
+        }));
+    }
+
+    fn set_edge_hovered(&mut self, idx: EdgeIndex<Ix>) {
+        let e = self.g.edge_mut(idx).unwrap();
+        e.set_hovered(true);
+
+        self.publish_event(Event::EdgeHover(PayloadEdgeHover { id: idx.index() }));
+    }
+
+    #[allow(unused_variables, clippy::unused_self)]
+    fn set_edge_double_clicked(&self, idx: EdgeIndex<Ix>) {
+        self.publish_event(Event::EdgeDoubleClick(PayloadEdgeDoubleClick {
+            id: idx.index(),
+        }));
+    }
+
     #[allow(unused_variables, clippy::unused_self)]
     fn set_edge_clicked(&self, idx: EdgeIndex<Ix>) {
-        #[cfg(feature = "events")]
         self.publish_event(Event::EdgeClick(PayloadEdgeClick { id: idx.index() }));
     }
 
+    #[allow(unused_variables, clippy::unused_self)]
+    fn set_edge_created(&self, start: NodeIndex<Ix>, end: NodeIndex<Ix>) {
+        self.publish_event(Event::EdgeCreate(PayloadEdgeCreate {
+            source: start.index(),
+            target: end.index(),
+        }));
+    }
+
     fn select_edge(&mut self, idx: EdgeIndex<Ix>) {
         let e = self.g.edge_mut(idx).unwrap();
         e.set_selected(true);
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::EdgeSelect(PayloadEdgeSelect { id: idx.index() }));
     }
 
@@ -553,7 +1792,6 @@ where
         let e = self.g.edge_mut(idx).unwrap();
         e.set_selected(false);
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::EdgeDeselect(PayloadEdgeDeselect { id: idx.index() }));
     }
 
@@ -568,6 +1806,7 @@ where
         for idx in selected_nodes {
             self.deselect_node(idx);
         }
+        self.g.set_selected_nodes(Vec::new());
     }
 
     fn deselect_all_edges(&mut self) {
@@ -579,33 +1818,63 @@ where
 
     fn move_node(&mut self, idx: NodeIndex<Ix>, delta: Vec2) {
         let n = self.g.node_mut(idx).unwrap();
-        let new_loc = n.location() + delta;
+        let old_loc = n.location();
+        let mut new_loc = old_loc + delta;
+        if let Some(bounds) = self.settings_interaction.drag_bounds {
+            new_loc = new_loc.clamp(bounds.min, bounds.max);
+        }
+        if new_loc == old_loc {
+            return;
+        }
         n.set_location(new_loc);
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeMove(PayloadNodeMove {
             id: idx.index(),
-            diff: delta.into(),
+            diff: (new_loc - old_loc).into(),
             new_pos: [new_loc.x, new_loc.y],
         }));
     }
 
-    fn set_drag_start(&mut self, idx: NodeIndex<Ix>) {
+    fn delete_node(&mut self, idx: NodeIndex<Ix>) {
+        let edges = self
+            .g
+            .incident_edges(idx)
+            .into_iter()
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>();
+        for e in edges {
+            self.g.remove_edge(e);
+
+            self.publish_event(Event::EdgeDelete(PayloadEdgeDelete { id: e.index() }));
+        }
+
+        self.g.remove_node(idx);
+
+        self.publish_event(Event::NodeDelete(PayloadNodeDelete { id: idx.index() }));
+    }
+
+    fn set_drag_start(&mut self, idx: NodeIndex<Ix>, meta: &mut Metadata) {
         let n = self.g.node_mut(idx).unwrap();
         n.set_dragged(true);
+        meta.drag_start = Some(n.location());
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::NodeDragStart(PayloadNodeDragStart {
             id: idx.index(),
         }));
     }
 
-    fn set_drag_end(&mut self, idx: NodeIndex<Ix>) {
+    #[allow(unused_variables)]
+    fn set_drag_end(&mut self, idx: NodeIndex<Ix>, meta: &mut Metadata) {
         let n = self.g.node_mut(idx).unwrap();
         n.set_dragged(false);
+        let to = n.location();
+        let from = meta.drag_start.take().unwrap_or(to);
 
-        #[cfg(feature = "events")]
-        self.publish_event(Event::NodeDragEnd(PayloadNodeDragEnd { id: idx.index() }));
+        self.publish_event(Event::NodeDragEnd(PayloadNodeDragEnd {
+            id: idx.index(),
+            from: from.into(),
+            to: to.into(),
+        }));
     }
 
     #[allow(unused_variables, clippy::unused_self)]
@@ -613,7 +1882,6 @@ where
         let diff = new_pan - meta.pan;
         meta.pan = new_pan;
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::Pan(PayloadPan {
             diff: diff.into(),
             new_pan: new_pan.into(),
@@ -625,14 +1893,191 @@ where
         let diff = new_zoom - meta.zoom;
         meta.zoom = new_zoom;
 
-        #[cfg(feature = "events")]
         self.publish_event(Event::Zoom(PayloadZoom { diff, new_zoom }));
     }
 
-    #[cfg(feature = "events")]
     fn publish_event(&self, event: Event) {
+        #[cfg(feature = "events")]
         if let Some(sender) = self.events_publisher {
-            sender.send(event).unwrap();
+            send_ignoring_disconnect(sender, event.clone());
+        }
+        if let Some(buffer) = &self.events_buffer {
+            buffer.borrow_mut().push(event);
+        }
+    }
+}
+
+/// Whether the optional modifier is set and held.
+fn modifier_held(modifier: Option<Modifiers>, modifiers: Modifiers) -> bool {
+    modifier.is_some_and(|m| modifiers.contains(m))
+}
+
+/// Returns scroll delta used to pan, vertical scrolling with shift held pans horizontally.
+fn scroll_pan_delta(delta: Vec2, modifiers: Modifiers) -> Vec2 {
+    if modifiers.shift {
+        Vec2::new(delta.x + delta.y, 0.)
+    } else {
+        delta
+    }
+}
+
+/// Returns movement from `loc` which keeps the moved point on the axis through `start` the point moved
+/// farther along, ties go to the horizontal axis.
+fn lock_to_axis(start: Pos2, loc: Pos2, delta: Vec2) -> Vec2 {
+    let moved = loc + delta - start;
+    let locked = if moved.x.abs() >= moved.y.abs() {
+        Vec2::new(moved.x, 0.)
+    } else {
+        Vec2::new(0., moved.y)
+    };
+
+    start + locked - loc
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "events")]
+    use crossbeam::channel::unbounded;
+    use petgraph::stable_graph::StableGraph;
+
+    use super::*;
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_publish_event_without_receiver() {
+        let mut g = Graph::<()>::new(StableGraph::default());
+        let (sender, receiver) = unbounded();
+        drop(receiver);
+
+        let view = GraphView::<()>::new(&mut g).with_events(&sender);
+        let mut meta = Metadata::default();
+        view.set_zoom(2., &mut meta);
+
+        assert_eq!(meta.zoom, 2.);
+    }
+
+    #[test]
+    fn test_event_buffer() {
+        let mut g = Graph::<()>::new(StableGraph::default());
+        let mut events = vec![];
+
+        let view = GraphView::<()>::new(&mut g).with_event_buffer(&mut events);
+        let mut meta = Metadata::default();
+        view.set_zoom(2., &mut meta);
+        view.set_ready();
+        drop(view);
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Zoom(PayloadZoom {
+                    diff: 1.,
+                    new_zoom: 2.
+                }),
+                Event::Ready
+            ]
+        );
+    }
+
+    #[test]
+    fn test_faded_removal_events() {
+        let mut sg = StableGraph::new();
+        let removed = sg.add_node(());
+        let other = sg.add_node(());
+        sg.add_edge(removed, other, ());
+        let mut g = Graph::<()>::from(&sg);
+        assert!(g.remove_node_faded(removed));
+        assert!(!g.remove_node_faded(NodeIndex::new(5)));
+
+        let mut events = vec![];
+        let mut view = GraphView::<()>::new(&mut g).with_event_buffer(&mut events);
+        assert!(!view.step_fades(1.));
+        drop(view);
+
+        assert!(g.node(removed).is_none());
+        assert_eq!(
+            events,
+            vec![
+                Event::EdgeDelete(PayloadEdgeDelete { id: 0 }),
+                Event::NodeDelete(PayloadNodeDelete {
+                    id: removed.index()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edge_curvature_fn() {
+        let mut sg = StableGraph::new();
+        let start = sg.add_node(());
+        let end = sg.add_node(());
+        let edge = sg.add_edge(start, end, 10.);
+        let mut g = Graph::<(), f32>::from(&sg);
+        let curvature = |g: &mut Graph<(), f32>| {
+            GraphView::<(), f32>::new(g)
+                .with_edge_curvature_fn(|c| *c)
+                .sync_state(&mut Metadata::default());
+            g.edge(edge).unwrap().curvature()
+        };
+        assert_eq!(curvature(&mut g), 10.);
+
+        *g.edge_mut(edge).unwrap().payload_mut() = -5.;
+        assert_eq!(curvature(&mut g), -5.);
+    }
+
+    #[test]
+    fn test_delete_selected_nodes() {
+        let mut sg = StableGraph::new();
+        let deleted = sg.add_node(());
+        let kept = sg.add_node(());
+        let other = sg.add_node(());
+        sg.add_edge(deleted, kept, ());
+        sg.add_edge(kept, other, ());
+        let mut g = Graph::<()>::from(&sg);
+        for idx in [deleted, kept] {
+            g.node_mut(idx).unwrap().set_selected(true);
         }
+        g.node_mut(kept).unwrap().set_interactable(false);
+        g.set_selected_nodes(vec![deleted, kept]);
+
+        GraphView::<()>::new(&mut g).delete_selected_nodes();
+
+        assert!(g.node(deleted).is_none());
+        assert!(!g.node(kept).unwrap().selected());
+        assert!(g.selected_nodes().is_empty());
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_pending_drag_captures_pan() {
+        let mut g = Graph::<()>::new(StableGraph::default());
+        let idx = g.add_node(());
+        let view = GraphView::<()>::new(&mut g);
+        let mut meta = Metadata::default();
+        assert!(!view.drag_captured(&meta));
+
+        // the pointer is pressed on the node but has not moved past the drag threshold
+        meta.drag_pending = Some((idx.index(), Vec2::new(1., 0.)));
+        assert!(view.drag_captured(&meta));
+    }
+
+    #[test]
+    fn test_scroll_pan_delta() {
+        let delta = Vec2::new(2., 5.);
+
+        assert_eq!(scroll_pan_delta(delta, Modifiers::NONE), delta);
+        assert_eq!(scroll_pan_delta(delta, Modifiers::SHIFT), Vec2::new(7., 0.));
+    }
+
+    #[test]
+    fn test_lock_to_axis() {
+        let start = Pos2::new(10., 10.);
+
+        let delta = lock_to_axis(start, start, Vec2::new(5., 2.));
+        assert_eq!(delta, Vec2::new(5., 0.));
+
+        // the node returns to the axis once the other one dominates
+        let delta = lock_to_axis(start, Pos2::new(15., 10.), Vec2::new(-5., 8.));
+        assert_eq!(delta, Vec2::new(-5., 8.));
     }
 }
@@ -47,7 +47,7 @@ where
 
     node_transform(&mut n);
 
-    g.g.add_node(n)
+    g.g().add_node(n)
 }
 
 /// Helper function which adds user's edge to the [`super::Graph`] instance.
@@ -97,7 +97,7 @@ where
 
     edge_transform(&mut edge);
 
-    g.g.add_edge(start, end, edge)
+    g.g().add_edge(start, end, edge)
 }
 
 /// Helper function which transforms [`petgraph::stable_graph::StableGraph`] into the [`super::Graph`] required by the [`super::GraphView`] widget.
@@ -0,0 +1,429 @@
+use std::{collections::HashMap, fmt::Write};
+
+use petgraph::{
+    stable_graph::{IndexType, NodeIndex, StableGraph},
+    visit::{EdgeRef, IntoEdgeReferences},
+    EdgeType,
+};
+
+use crate::{DisplayEdge, DisplayNode, Graph, Node};
+
+/// Parses a basic subset of [Graphviz DOT](https://graphviz.org/doc/info/lang.html) into a [`Graph`].
+///
+/// Node ids become node payloads and labels, edges get their `label` attribute as payload or an empty string.
+/// Supported are node and edge statements including edge chains (`a -> b -> c`), both `->` and `--` edge operators,
+/// quoted ids, comments and subgraph blocks whose statements are flattened into the graph. The `label` attribute
+/// overrides the label of nodes and edges, all other attributes, ports and graph level statements are ignored.
+///
+/// All nodes are placed at the origin, so a layout is expected to position them.
+///
+/// # Example
+/// ```
+/// use egui_graphs::io::from_dot;
+///
+/// let g = from_dot(r#"digraph { a -> b -> c; b -> d [label="bd"]; }"#);
+///
+/// assert_eq!(g.node_count(), 4);
+/// assert_eq!(g.edge_count(), 3);
+/// ```
+pub fn from_dot(src: &str) -> Graph<String, String> {
+    let tokens = tokenize(src);
+    let mut parser = Parser {
+        g: Graph::new(StableGraph::default()),
+        nodes: HashMap::new(),
+    };
+
+    // skip the header `[strict] (graph | digraph) [id]`
+    let start = tokens
+        .iter()
+        .position(|t| *t == Token::LBrace)
+        .map_or(0, |p| p + 1);
+
+    let mut tokens = tokens[start..].iter().peekable();
+    while let Some(t) = tokens.next() {
+        let Token::Id(id) = t else {
+            continue;
+        };
+
+        match tokens.peek() {
+            // graph level attribute `id = value`
+            Some(Token::Eq) => {
+                tokens.next();
+                tokens.next();
+                continue;
+            }
+            // default attributes `graph | node | edge [..]`
+            Some(Token::LBracket) if matches!(id.as_str(), "graph" | "node" | "edge") => {
+                attrs(&mut tokens);
+                continue;
+            }
+            _ => {}
+        }
+
+        // subgraph blocks are flattened
+        if id == "subgraph" {
+            if let Some(Token::Id(_)) = tokens.peek() {
+                tokens.next();
+            }
+            continue;
+        }
+
+        let mut chain = vec![id.clone()];
+        skip_port(&mut tokens);
+        while tokens.next_if_eq(&&Token::Edge).is_some() {
+            let Some(Token::Id(next)) = tokens.next() else {
+                break;
+            };
+            chain.push(next.clone());
+            skip_port(&mut tokens);
+        }
+
+        let label = attrs(&mut tokens).remove("label");
+        parser.statement(&chain, label);
+    }
+
+    parser.g
+}
+
+/// Writes the graph as [Graphviz DOT](https://graphviz.org/doc/info/lang.html).
+///
+/// Produces `digraph` with `->` edges or `graph` with `--` edges depending on [`Graph::is_directed`].
+/// Nodes are identified by their indices and labeled with `node_label`, edges are labeled with their labels.
+/// Node locations are written as `pos` attributes with the y axis flipped, so Graphviz tools which respect
+/// positions (e.g. `neato -n`) keep the layout.
+///
+/// # Example
+/// ```
+/// use egui_graphs::io::{from_dot, to_dot};
+///
+/// let g = from_dot("digraph { a -> b }");
+/// let dot = to_dot(&g, |n| n.payload().clone());
+///
+/// assert!(dot.starts_with("digraph {"));
+/// assert!(dot.contains("0 -> 1"));
+/// ```
+pub fn to_dot<N, E, Ty, Ix, Dn, De>(
+    g: &Graph<N, E, Ty, Ix, Dn, De>,
+    node_label: impl Fn(&Node<N, E, Ty, Ix, Dn>) -> String,
+) -> String
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    let (kind, op) = if g.is_directed() {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    let mut res = format!("{kind} {{\n");
+    for (idx, n) in g.nodes_iter() {
+        let loc = n.location();
+        let _ = writeln!(
+            res,
+            "    {} [label=\"{}\", pos=\"{},{}\"];",
+            idx.index(),
+            escape(&node_label(n)),
+            loc.x,
+            -loc.y
+        );
+    }
+    for e in g.g.edge_references() {
+        let _ = writeln!(
+            res,
+            "    {} {op} {} [label=\"{}\"];",
+            e.source().index(),
+            e.target().index(),
+            escape(&e.weight().label())
+        );
+    }
+    res.push('}');
+
+    res
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+struct Parser {
+    g: Graph<String, String>,
+    nodes: HashMap<String, NodeIndex>,
+}
+
+impl Parser {
+    fn statement(&mut self, chain: &[String], label: Option<String>) {
+        if let [id] = chain {
+            let idx = self.node(id);
+            if let Some(label) = label {
+                self.g.node_mut(idx).unwrap().set_label(label);
+            }
+            return;
+        }
+
+        for pair in chain.windows(2) {
+            let start = self.node(&pair[0]);
+            let end = self.node(&pair[1]);
+            match &label {
+                Some(label) => self
+                    .g
+                    .add_edge_with_label(start, end, label.clone(), label.clone()),
+                None => self.g.add_edge(start, end, String::new()),
+            };
+        }
+    }
+
+    fn node(&mut self, id: &str) -> NodeIndex {
+        if let Some(idx) = self.nodes.get(id) {
+            return *idx;
+        }
+
+        let idx = self.g.add_node_with_label(id.to_string(), id.to_string());
+        self.nodes.insert(id.to_string(), idx);
+        idx
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Edge,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Eq,
+    Colon,
+    Separator,
+}
+
+/// Collects attribute lists `[a = b, c = d][e = f]` following the current token.
+fn attrs<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a Token>>,
+) -> HashMap<String, String> {
+    let mut res = HashMap::new();
+    while tokens.next_if_eq(&&Token::LBracket).is_some() {
+        while let Some(t) = tokens.next() {
+            match t {
+                Token::RBracket => break,
+                Token::Id(key) => {
+                    if tokens.next_if_eq(&&Token::Eq).is_none() {
+                        continue;
+                    }
+                    if let Some(Token::Id(value)) = tokens.next() {
+                        res.insert(key.clone(), value.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    res
+}
+
+/// Skips node port and compass point `:port:compass`.
+fn skip_port<'a>(tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a Token>>) {
+    while tokens.next_if_eq(&&Token::Colon).is_some() {
+        tokens.next();
+    }
+}
+
+fn tokenize(src: &str) -> Vec<Token> {
+    let mut res = vec![];
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '=' => Token::Eq,
+            ':' => Token::Colon,
+            ';' | ',' => Token::Separator,
+            '-' if chars.next_if(|c| *c == '>' || *c == '-').is_some() => Token::Edge,
+            '#' => {
+                chars.by_ref().find(|c| *c == '\n');
+                continue;
+            }
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                chars.by_ref().find(|c| *c == '\n');
+                continue;
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                continue;
+            }
+            '"' => Token::Id(quoted(&mut chars)),
+            '<' => Token::Id(html(&mut chars)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    id.push(c);
+                }
+                Token::Id(id)
+            }
+            _ => continue,
+        };
+        res.push(token);
+    }
+
+    res
+}
+
+/// Reads a quoted string after the opening quote.
+/// Only escaped quotes, backslashes and line continuations are unescaped.
+fn quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut res = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' if chars.next_if_eq(&'"').is_some() => res.push('"'),
+            '\\' if chars.next_if_eq(&'\\').is_some() => res.push('\\'),
+            '\\' if chars.next_if_eq(&'\n').is_some() => {}
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// Reads an HTML string after the opening angle bracket keeping the nested brackets.
+fn html(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut res = String::new();
+    let mut depth = 1;
+    for c in chars.by_ref() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        res.push(c);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Pos2;
+    use petgraph::Undirected;
+
+    use super::*;
+
+    fn labels(g: &Graph<String, String>) -> Vec<String> {
+        g.nodes_iter().map(|(_, n)| n.label()).collect()
+    }
+
+    #[test]
+    fn test_from_dot_directed_chain() {
+        let g = from_dot("digraph G { a -> b -> c; a -> c }");
+
+        assert_eq!(labels(&g), vec!["a", "b", "c"]);
+        assert_eq!(g.edge_count(), 3);
+        let ends =
+            g.g.edge_indices()
+                .map(|e| g.edge_endpoints(e).unwrap())
+                .map(|(s, e)| (s.index(), e.index()))
+                .collect::<Vec<_>>();
+        assert_eq!(ends, vec![(0, 1), (1, 2), (0, 2)]);
+    }
+
+    #[test]
+    fn test_from_dot_undirected() {
+        let g = from_dot("strict graph { x -- y; y -- z }");
+
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_from_dot_attributes() {
+        let g = from_dot(
+            r#"
+            digraph {
+                // defaults and graph attributes are ignored
+                rankdir = LR;
+                node [shape=box];
+                "node a" [label="A \"quoted\"", color=red];
+                "node a" -> b [label=ab, weight=2];
+                b:port:n -> c [style=dashed];
+            }
+            "#,
+        );
+
+        assert_eq!(labels(&g), vec!["A \"quoted\"", "b", "c"]);
+        let (_, a) = g.nodes_iter().next().unwrap();
+        assert_eq!(a.payload(), "node a");
+
+        let edges = g.edges_iter().map(|(_, e)| e).collect::<Vec<_>>();
+        assert_eq!(edges[0].payload(), "ab");
+        assert_eq!(edges[0].label(), "ab");
+        assert_eq!(edges[1].payload(), "");
+    }
+
+    #[test]
+    fn test_to_dot_round_trip() {
+        let g = from_dot(r#"digraph { a -> b [label="a \"to\" b\\"]; b -> c }"#);
+        let dot = to_dot(&g, Node::label);
+
+        let restored = from_dot(&dot);
+        assert_eq!(labels(&restored), vec!["a", "b", "c"]);
+        let edges = restored
+            .edges_iter()
+            .map(|(_, e)| e.label())
+            .collect::<Vec<_>>();
+        assert_eq!(edges, vec!["a \"to\" b\\", "edge 1"]);
+    }
+
+    #[test]
+    fn test_to_dot_undirected() {
+        let mut g = StableGraph::<(), (), Undirected>::default();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, ());
+        let mut g = Graph::<(), (), Undirected>::from(&g);
+        g.node_mut(b).unwrap().set_location(Pos2::new(10., 20.));
+
+        let dot = to_dot(&g, |_| "n".to_string());
+
+        assert!(dot.starts_with("graph {"));
+        assert!(dot.contains("1 [label=\"n\", pos=\"10,-20\"];"));
+        assert!(dot.contains("0 -- 1"));
+        assert!(!dot.contains("->"));
+    }
+
+    #[test]
+    fn test_from_dot_comments_and_subgraphs() {
+        let g = from_dot(
+            "digraph {
+                # preprocessor style comment
+                /* block -> comment */
+                subgraph cluster_0 { a -> b }
+                { c }
+                -1 -> 2.5
+            }",
+        );
+
+        assert_eq!(labels(&g), vec!["a", "b", "c", "-1", "2.5"]);
+        assert_eq!(g.edge_count(), 2);
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use egui::Pos2;
+use petgraph::{
+    stable_graph::{IndexType, NodeIndex, StableGraph},
+    visit::{EdgeRef, IntoEdgeReferences},
+    EdgeType,
+};
+use serde::{de::DeserializeOwned, de::Error, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{DisplayEdge, DisplayNode, Graph};
+
+/// Node-link document as used by d3 and cytoscape datasets.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "N: Deserialize<'de>, E: Deserialize<'de>"))]
+struct NodeLink<N, E> {
+    #[serde(default)]
+    directed: bool,
+    nodes: Vec<JsonNode<N>>,
+    #[serde(default)]
+    links: Vec<JsonLink<E>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonNode<N> {
+    #[serde(default)]
+    id: Option<Value>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(flatten)]
+    data: N,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonLink<E> {
+    source: Value,
+    target: Value,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(flatten)]
+    data: E,
+}
+
+/// Reads a graph from the JSON node-link format.
+///
+/// The document is an object with a `nodes` array and a `links` array. Nodes are referenced from links
+/// by their `id` field, nodes without an id are referenced by their position in the `nodes` array.
+/// Node `x` and `y` fields become node locations, missing positions default to the origin so a layout can place them.
+/// Optional `label` fields override default labels of nodes and edges. All other fields of nodes and links
+/// are deserialized into node and edge payloads, so payloads have to deserialize from a map,
+/// like [`serde_json::Value`], a struct or `()`.
+///
+/// # Errors
+/// Returns an error if the document is malformed or a link references an unknown node.
+///
+/// # Example
+/// ```
+/// use egui_graphs::io::from_json;
+/// use petgraph::Directed;
+/// use serde_json::Value;
+///
+/// let g = from_json::<Value, Value, Directed>(
+///     r#"{
+///         "nodes": [{"id": "a", "group": 1}, {"id": "b", "x": 10, "y": 20}],
+///         "links": [{"source": "a", "target": "b", "value": 5}]
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(g.node_count(), 2);
+/// assert_eq!(g.edge_count(), 1);
+/// ```
+pub fn from_json<N, E, Ty>(src: &str) -> Result<Graph<N, E, Ty>, serde_json::Error>
+where
+    N: Clone + DeserializeOwned,
+    E: Clone + DeserializeOwned,
+    Ty: EdgeType,
+{
+    let doc: NodeLink<N, E> = serde_json::from_str(src)?;
+
+    let mut g = Graph::new(StableGraph::default());
+    let mut ids = HashMap::with_capacity(doc.nodes.len());
+    for (i, n) in doc.nodes.into_iter().enumerate() {
+        let id = n.id.unwrap_or_else(|| Value::from(i));
+        let label = n.label.unwrap_or_else(|| match &id {
+            Value::String(s) => s.clone(),
+            id => id.to_string(),
+        });
+
+        let idx = g.add_node_with_label_and_location(n.data, label, Pos2::new(n.x, n.y));
+        ids.insert(id.to_string(), idx);
+    }
+
+    let node = |id: &Value| -> Result<NodeIndex, serde_json::Error> {
+        ids.get(&id.to_string())
+            .copied()
+            .ok_or_else(|| serde_json::Error::custom(format!("unknown node id {id}")))
+    };
+    for l in doc.links {
+        let (start, end) = (node(&l.source)?, node(&l.target)?);
+        match l.label {
+            Some(label) => g.add_edge_with_label(start, end, l.data, label),
+            None => g.add_edge(start, end, l.data),
+        };
+    }
+
+    Ok(g)
+}
+
+/// Writes the graph in the JSON node-link format read by [`from_json`].
+///
+/// Nodes get their indices as ids, labels and locations as `x` and `y`. Links reference node ids
+/// in `source` and `target` and carry edge labels. Payloads are flattened into node and link objects,
+/// so they have to serialize as a map or `()`.
+///
+/// # Errors
+/// Returns an error if a payload can not be serialized as a map.
+///
+/// # Example
+/// ```
+/// use egui_graphs::{io::to_json, Graph};
+/// use petgraph::stable_graph::StableGraph;
+///
+/// let mut g = StableGraph::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, ());
+///
+/// let json = to_json(&Graph::<()>::from(&g)).unwrap();
+///
+/// assert!(json.contains(r#""links":[{"source":0,"target":1"#));
+/// ```
+pub fn to_json<N, E, Ty, Ix, Dn, De>(
+    g: &Graph<N, E, Ty, Ix, Dn, De>,
+) -> Result<String, serde_json::Error>
+where
+    N: Clone + Serialize,
+    E: Clone + Serialize,
+    Ty: EdgeType,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    let nodes = g
+        .nodes_iter()
+        .map(|(idx, n)| JsonNode {
+            id: Some(Value::from(idx.index())),
+            label: Some(n.label()),
+            x: n.location().x,
+            y: n.location().y,
+            data: n.payload(),
+        })
+        .collect();
+
+    let links =
+        g.g.edge_references()
+            .map(|e| JsonLink {
+                source: Value::from(e.source().index()),
+                target: Value::from(e.target().index()),
+                label: Some(e.weight().label()),
+                data: e.weight().payload(),
+            })
+            .collect();
+
+    serde_json::to_string(&NodeLink {
+        directed: g.is_directed(),
+        nodes,
+        links,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{Directed, Undirected};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_from_json_d3() {
+        let g = from_json::<Value, Value, Directed>(
+            r#"{
+                "nodes": [
+                    {"id": "Myriel", "group": 1, "x": 1.5, "y": -2},
+                    {"id": "Napoleon", "group": 1}
+                ],
+                "links": [{"source": "Napoleon", "target": "Myriel", "value": 1}]
+            }"#,
+        )
+        .unwrap();
+
+        let nodes = g.nodes_iter().map(|(_, n)| n).collect::<Vec<_>>();
+        assert_eq!(nodes[0].label(), "Myriel");
+        assert_eq!(nodes[0].location(), Pos2::new(1.5, -2.));
+        assert_eq!(*nodes[0].payload(), json!({"group": 1}));
+        assert_eq!(nodes[1].location(), Pos2::ZERO);
+
+        let (idx, e) = g.edges_iter().next().unwrap();
+        assert_eq!(*e.payload(), json!({"value": 1}));
+        let (start, end) = g.edge_endpoints(idx).unwrap();
+        assert_eq!((start.index(), end.index()), (1, 0));
+    }
+
+    #[test]
+    fn test_from_json_index_ids() {
+        let g = from_json::<(), (), Undirected>(
+            r#"{"nodes": [{}, {}, {}], "links": [{"source": 2, "target": 0}]}"#,
+        )
+        .unwrap();
+
+        let (idx, _) = g.edges_iter().next().unwrap();
+        let (start, end) = g.edge_endpoints(idx).unwrap();
+        assert_eq!((start.index(), end.index()), (2, 0));
+    }
+
+    #[test]
+    fn test_from_json_unknown_id() {
+        let res = from_json::<(), (), Directed>(
+            r#"{"nodes": [{"id": "a"}], "links": [{"source": "a", "target": "b"}]}"#,
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut g = Graph::<Value, Value>::new(StableGraph::default());
+        let a = g.add_node_with_location(json!({"name": "a"}), Pos2::new(10., 20.));
+        let b = g.add_node_with_location(json!({}), Pos2::new(-5., 0.5));
+        g.add_edge_with_label(b, a, json!({"weight": 2}), "b-a".to_string());
+        g.add_edge(a, a, json!({}));
+
+        let restored = from_json::<Value, Value, Directed>(&to_json(&g).unwrap()).unwrap();
+
+        for ((_, n), (_, restored_n)) in g.nodes_iter().zip(restored.nodes_iter()) {
+            assert_eq!(n.location(), restored_n.location());
+            assert_eq!(n.label(), restored_n.label());
+            assert_eq!(n.payload(), restored_n.payload());
+        }
+        for ((idx, e), (restored_idx, restored_e)) in g.edges_iter().zip(restored.edges_iter()) {
+            assert_eq!(g.edge_endpoints(idx), restored.edge_endpoints(restored_idx));
+            assert_eq!(e.label(), restored_e.label());
+            assert_eq!(e.payload(), restored_e.payload());
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod dot;
+#[cfg(feature = "json")]
+mod json;
+
+pub use dot::{from_dot, to_dot};
+#[cfg(feature = "json")]
+pub use json::{from_json, to_json};
@@ -0,0 +1,68 @@
+use egui::Pos2;
+use serde::{Deserialize, Serialize};
+
+use crate::MaybeSync;
+
+/// Defines how distances between nodes are measured and how strong the forces applied at these
+/// distances are.
+///
+/// Implement it to use the [`ForceDirected`](super::ForceDirected) layout with non-standard coordinate
+/// systems or preferred edge lengths. With the `rayon` feature forces are shared between threads
+/// and have to be `Sync`. The force is kept in the layout [`State`](super::State), so to be used with
+/// [`crate::GraphView`] it also has to be serializable.
+pub trait Force: Default + MaybeSync {
+    /// Distance between two node locations. Euclidean by default.
+    fn distance(&self, a: Pos2, b: Pos2) -> f32 {
+        a.distance(b)
+    }
+
+    /// Magnitude of the force pushing apart every pair of nodes at the given distance.
+    fn repulsion(&self, dist: f32) -> f32;
+
+    /// Magnitude of the force pulling together nodes connected with an edge at the given distance.
+    fn attraction(&self, dist: f32) -> f32;
+}
+
+/// Fruchterman-Reingold spring model in euclidean space.
+///
+/// Connected nodes settle at `ideal_length` from each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Euclidean {
+    pub ideal_length: f32,
+}
+
+impl Default for Euclidean {
+    fn default() -> Self {
+        Self { ideal_length: 50. }
+    }
+}
+
+impl Force for Euclidean {
+    fn repulsion(&self, dist: f32) -> f32 {
+        self.ideal_length * self.ideal_length / dist
+    }
+
+    fn attraction(&self, dist: f32) -> f32 {
+        dist * dist / self.ideal_length
+    }
+}
+
+/// The same spring model as [`Euclidean`], but distances are measured as `|dx| + |dy|`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manhattan {
+    pub springs: Euclidean,
+}
+
+impl Force for Manhattan {
+    fn distance(&self, a: Pos2, b: Pos2) -> f32 {
+        (a.x - b.x).abs() + (a.y - b.y).abs()
+    }
+
+    fn repulsion(&self, dist: f32) -> f32 {
+        self.springs.repulsion(dist)
+    }
+
+    fn attraction(&self, dist: f32) -> f32 {
+        self.springs.attraction(dist)
+    }
+}
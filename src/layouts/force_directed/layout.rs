@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+
+use egui::util::id_type_map::SerializableAny;
+use egui::{Pos2, Vec2};
+use petgraph::{
+    stable_graph::{IndexType, NodeIndex},
+    visit::{EdgeRef, IntoEdgeReferences},
+    EdgeType,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    layouts::{Layout, LayoutState},
+    DisplayEdge, DisplayNode, Graph,
+};
+
+use super::{Euclidean, Force};
+
+/// Number of frames the simulation runs for before it stops.
+const ITERATIONS: usize = 300;
+/// Maximum distance in canvas units a node is moved by in the first iteration.
+const INITIAL_TEMPERATURE: f32 = 50.;
+/// Minimal distance between nodes used to compute forces to avoid division by zero.
+const MIN_DISTANCE: f32 = 0.01;
+/// Minimal edge weight, smaller ones including zero and negative are clamped to it.
+const MIN_WEIGHT: f32 = 0.01;
+/// Average movement of a node per step in canvas units below which the graph is considered settled.
+const STABLE_MOVEMENT: f32 = 0.01;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct State<F: Force = Euclidean> {
+    iteration: usize,
+    movement: Option<f32>,
+    incremental: Option<f32>,
+    force: F,
+}
+
+impl<F: Force> State<F> {
+    /// Forces the simulation runs with, e.g. [`Euclidean`] with a custom preferred edge length.
+    ///
+    /// Default: `F::default()`
+    pub fn with_force(mut self, force: F) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn force(&self) -> &F {
+        &self.force
+    }
+
+    /// Enables incremental layout. While there are dirty nodes, see `Node::set_dirty`, the simulation
+    /// restarts and other nodes move only by the `mobility` fraction in range `[0, 1]` of their step,
+    /// so new nodes settle without disturbing the established layout. Dirty flags are cleared once
+    /// the graph is stable.
+    ///
+    /// Default: `None`
+    pub fn with_incremental(mut self, mobility: Option<f32>) -> Self {
+        self.incremental = mobility;
+        self
+    }
+
+    /// Total distance nodes moved during the last step, `None` if no steps were made yet.
+    pub fn movement(&self) -> Option<f32> {
+        self.movement
+    }
+
+    /// Whether the last step moved nodes by less than `epsilon` in total or the simulation cooled down.
+    pub fn is_stable(&self, epsilon: f32) -> bool {
+        self.iteration >= ITERATIONS || self.movement.is_some_and(|m| m < epsilon)
+    }
+}
+
+impl<F: Force + SerializableAny> LayoutState for State<F> {}
+
+/// Force-directed layout. Nodes repel each other while edges pull connected nodes together.
+///
+/// Runs one simulation step per frame, cooling down until it stops after a fixed number of steps
+/// or earlier once the graph settles.
+/// Forces are defined by `F`, see [`Force`]. Attraction along an edge is scaled by its weight,
+/// see [`crate::Graph::set_edge_weights`]. Nodes with locations set by the user, e.g. dragged ones,
+/// are not moved but still affect other nodes.
+///
+/// The force is part of the [`State`], configure it with [`State::with_force`] and
+/// [`crate::GraphView::set_layout_state`] to use it in the widget.
+#[derive(Debug, Default)]
+pub struct ForceDirected<F: Force = Euclidean> {
+    state: State<F>,
+}
+
+impl<F: Force + SerializableAny> Layout<State<F>> for ForceDirected<F> {
+    fn next<N, E, Ty, Ix, Dn, De>(&mut self, g: &mut Graph<N, E, Ty, Ix, Dn, De>)
+    where
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        Dn: DisplayNode<N, E, Ty, Ix>,
+        De: DisplayEdge<N, E, Ty, Ix, Dn>,
+    {
+        let epsilon = STABLE_MOVEMENT * g.node_count() as f32;
+        let incremental = self.state.incremental.is_some() && has_dirty(g);
+        if incremental && self.is_stable(epsilon) {
+            self.state.iteration = 0;
+            self.state.movement = None;
+        }
+
+        if self.is_stable(epsilon) {
+            return;
+        }
+
+        self.step(g);
+
+        if incremental && self.is_stable(epsilon) {
+            g.g.node_weights_mut().for_each(|n| n.set_dirty(false));
+        }
+    }
+
+    fn state(&self) -> State<F> {
+        self.state.clone()
+    }
+
+    fn from_state(state: State<F>) -> impl Layout<State<F>> {
+        Self { state }
+    }
+}
+
+impl<F: Force> ForceDirected<F> {
+    /// Creates the layout running with the given force.
+    pub fn new(force: F) -> Self {
+        Self {
+            state: State::default().with_force(force),
+        }
+    }
+
+    /// Makes a single simulation step and returns the total distance nodes moved.
+    ///
+    /// Steps after the simulation cooled down do nothing and return `0`.
+    pub fn step<N, E, Ty, Ix, Dn, De>(&mut self, g: &mut Graph<N, E, Ty, Ix, Dn, De>) -> f32
+    where
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        Dn: DisplayNode<N, E, Ty, Ix>,
+        De: DisplayEdge<N, E, Ty, Ix, Dn>,
+    {
+        if self.state.iteration >= ITERATIONS {
+            return 0.;
+        }
+
+        let nodes = g
+            .nodes_iter()
+            .map(|(idx, n)| (idx, n.location()))
+            .collect::<Vec<_>>();
+        let positions = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (idx, _))| (*idx, i))
+            .collect::<HashMap<NodeIndex<Ix>, usize>>();
+        let locations = nodes.iter().map(|(_, loc)| *loc).collect::<Vec<_>>();
+        let mut displacement = self.repulsion(&locations);
+
+        for e in g.g.edge_references() {
+            let (i, j) = (positions[&e.source()], positions[&e.target()]);
+            if i == j {
+                continue;
+            }
+
+            let (dir, dist) = self.direction(nodes[i].1, nodes[j].1, i);
+            let weight = e.weight().weight().max(MIN_WEIGHT);
+            let shift = dir * self.state.force.attraction(dist) * weight;
+            displacement[i] -= shift;
+            displacement[j] += shift;
+        }
+
+        let temperature =
+            INITIAL_TEMPERATURE * (1. - self.state.iteration as f32 / ITERATIONS as f32);
+        let mobility = self
+            .state
+            .incremental
+            .filter(|_| has_dirty(g))
+            .map(|m| m.clamp(0., 1.));
+        let mut movement = 0.;
+        for ((idx, loc), d) in nodes.into_iter().zip(displacement) {
+            let Some(n) = g.node_mut(idx) else {
+                continue;
+            };
+            if n.dragged() || n.location_user().is_some() {
+                continue;
+            }
+
+            let mut step = d.normalized() * d.length().min(temperature);
+            if let (Some(mobility), false) = (mobility, n.dirty()) {
+                step *= mobility;
+            }
+            n.set_layout_location(loc + step);
+            movement += step.length();
+        }
+
+        self.state.iteration += 1;
+        self.state.movement = Some(movement);
+        movement
+    }
+
+    /// See [`State::is_stable`].
+    pub fn is_stable(&self, epsilon: f32) -> bool {
+        self.state.is_stable(epsilon)
+    }
+
+    /// Steps until the graph is stable with the given `epsilon` or `max_iterations` steps are made.
+    /// Returns the number of steps made.
+    pub fn run_until_stable<N, E, Ty, Ix, Dn, De>(
+        &mut self,
+        g: &mut Graph<N, E, Ty, Ix, Dn, De>,
+        max_iterations: usize,
+        epsilon: f32,
+    ) -> usize
+    where
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        Dn: DisplayNode<N, E, Ty, Ix>,
+        De: DisplayEdge<N, E, Ty, Ix, Dn>,
+    {
+        let mut steps = 0;
+        while steps < max_iterations && !self.is_stable(epsilon) {
+            self.step(g);
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// Displacements of the nodes at `locations` pushed apart by every other node.
+    ///
+    /// Every pair of nodes is visited once and the force is applied to both of them.
+    #[cfg(not(feature = "rayon"))]
+    fn repulsion(&self, locations: &[Pos2]) -> Vec<Vec2> {
+        let mut displacement = vec![Vec2::ZERO; locations.len()];
+        for i in 0..locations.len() {
+            for j in (i + 1)..locations.len() {
+                let (dir, dist) = self.direction(locations[i], locations[j], i);
+                let shift = dir * self.state.force.repulsion(dist);
+                displacement[i] += shift;
+                displacement[j] -= shift;
+            }
+        }
+
+        displacement
+    }
+
+    /// Displacements of the nodes at `locations` pushed apart by every other node.
+    ///
+    /// Nodes are computed independently on the rayon thread pool, so every pair is visited twice.
+    /// Forces of a node are summed in the node order, which keeps the result the same between runs.
+    #[cfg(feature = "rayon")]
+    fn repulsion(&self, locations: &[Pos2]) -> Vec<Vec2> {
+        use rayon::prelude::*;
+
+        (0..locations.len())
+            .into_par_iter()
+            .map(|i| {
+                (0..locations.len())
+                    .filter(|j| *j != i)
+                    .fold(Vec2::ZERO, |acc, j| {
+                        // the same direction and seed as for the pair visited once
+                        let (first, second) = (i.min(j), i.max(j));
+                        let (dir, dist) =
+                            self.direction(locations[first], locations[second], first);
+                        let shift = dir * self.state.force.repulsion(dist);
+                        if i == first {
+                            acc + shift
+                        } else {
+                            acc - shift
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns unit direction from `b` to `a` and distance between them measured by the force.
+    /// Coincident nodes are pushed apart in a direction derived from `seed`.
+    fn direction(&self, a: Pos2, b: Pos2, seed: usize) -> (Vec2, f32) {
+        let delta = a - b;
+        if delta.length() < MIN_DISTANCE {
+            return (Vec2::angled(seed as f32), MIN_DISTANCE);
+        }
+
+        (
+            delta.normalized(),
+            self.state.force.distance(a, b).max(MIN_DISTANCE),
+        )
+    }
+}
+
+/// Whether any node waits to be placed by the incremental layout.
+fn has_dirty<N, E, Ty, Ix, Dn, De>(g: &Graph<N, E, Ty, Ix, Dn, De>) -> bool
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    Dn: DisplayNode<N, E, Ty, Ix>,
+    De: DisplayEdge<N, E, Ty, Ix, Dn>,
+{
+    g.nodes_iter().any(|(_, n)| n.dirty())
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::stable_graph::StableGraph;
+
+    use super::*;
+    use crate::{layouts::force_directed::Manhattan, to_graph, DefaultEdgeShape, DefaultNodeShape};
+
+    fn settle<F: Force>(force: F, weight: f32) -> f32 {
+        let mut sg: StableGraph<(), f32> = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        sg.add_edge(a, b, weight);
+        let mut g = to_graph::<_, _, _, _, DefaultNodeShape, DefaultEdgeShape>(&sg);
+        g.set_edge_weights(|e| *e.payload());
+        g.node_mut(b)
+            .unwrap()
+            .set_layout_location(Pos2::new(200., 0.));
+
+        let mut layout = ForceDirected::new(force);
+        layout.run_until_stable(&mut g, ITERATIONS, 0.);
+
+        g.node(a)
+            .unwrap()
+            .location()
+            .distance(g.node(b).unwrap().location())
+    }
+
+    #[test]
+    fn test_connected_nodes_settle_at_ideal_length() {
+        assert!((settle(Euclidean::default(), 1.) - 50.).abs() < 1.);
+        // nodes are placed on a horizontal line so both metrics agree
+        assert!((settle(Manhattan::default(), 1.) - 50.).abs() < 1.);
+
+        let force = Euclidean { ideal_length: 80. };
+        assert!((settle(force, 1.) - 80.).abs() < 1.);
+    }
+
+    #[test]
+    fn test_state_keeps_force() {
+        let state = ForceDirected::new(Euclidean { ideal_length: 80. }).state();
+        let layout = ForceDirected::<Euclidean>::from_state(state);
+        assert_eq!(layout.state().force().ideal_length, 80.);
+    }
+
+    #[test]
+    fn test_skips_pinned_nodes() {
+        let mut g = Graph::<()>::default();
+        let a = g.add_node(());
+        let pinned = Pos2::new(0., 60.);
+        let b = g.add_node(());
+        g.node_mut(b).unwrap().set_location(pinned);
+        g.add_edge(a, b, ());
+
+        let mut layout = ForceDirected::<Euclidean>::default();
+        let movement = layout.step(&mut g);
+        assert_eq!(g.node(b).unwrap().location(), pinned);
+        assert_eq!(movement, g.node(a).unwrap().location().distance(Pos2::ZERO));
+
+        // pinned nodes do not keep the simulation running
+        g.node_mut(a).unwrap().set_location(Pos2::ZERO);
+        layout.step(&mut g);
+        assert!(layout.is_stable(0.02));
+    }
+
+    #[test]
+    fn test_stops_when_stable() {
+        let mut sg: StableGraph<(), ()> = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        sg.add_edge(a, b, ());
+        let mut g = to_graph::<_, _, _, _, DefaultNodeShape, DefaultEdgeShape>(&sg);
+        g.node_mut(b)
+            .unwrap()
+            .set_layout_location(Pos2::new(60., 0.));
+
+        let mut layout = ForceDirected::<Euclidean>::default();
+        assert!(!layout.is_stable(1.));
+        let steps = layout.run_until_stable(&mut g, ITERATIONS, 0.02);
+        assert!(steps < ITERATIONS);
+        assert!(layout.state().movement().unwrap() < 0.02);
+
+        // the widget stops stepping a settled graph
+        let loc = g.node(b).unwrap().location();
+        layout.next(&mut g);
+        assert_eq!(layout.state().iteration, steps);
+        assert_eq!(g.node(b).unwrap().location(), loc);
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut sg: StableGraph<(), ()> = StableGraph::new();
+        let a = sg.add_node(());
+        let b = sg.add_node(());
+        sg.add_edge(a, b, ());
+        let mut g = to_graph::<_, _, _, _, DefaultNodeShape, DefaultEdgeShape>(&sg);
+        g.node_mut(b)
+            .unwrap()
+            .set_layout_location(Pos2::new(60., 0.));
+
+        let mut layout =
+            ForceDirected::<Euclidean>::from_state(State::default().with_incremental(Some(0.)));
+        for _ in 0..ITERATIONS {
+            layout.next(&mut g);
+        }
+        let settled = [a, b].map(|idx| g.node(idx).unwrap().location());
+
+        let start = Pos2::new(1., 1.);
+        let c = g.add_node_with_location((), start);
+        g.add_edge(a, c, ());
+        assert!(g.node(c).unwrap().dirty());
+        for _ in 0..ITERATIONS {
+            layout.next(&mut g);
+        }
+
+        assert_eq!([a, b].map(|idx| g.node(idx).unwrap().location()), settled);
+        let placed = g.node(c).unwrap().location();
+        assert!((placed - start).length() > 10.);
+        assert!((placed - settled[0]).length() > 10.);
+        assert!(!g.node(c).unwrap().dirty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_step_deterministic() {
+        let mut g = crate::random_graph(200, 200);
+        let indices = g.g.node_indices().collect::<Vec<_>>();
+        for (i, idx) in indices.iter().enumerate() {
+            let loc = Pos2::new((i % 15) as f32, (i / 15) as f32);
+            g.node_mut(*idx).unwrap().set_layout_location(loc);
+        }
+
+        let run = |threads| {
+            let mut g = g.clone();
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut layout = ForceDirected::<Euclidean>::default();
+            let movements =
+                pool.install(|| (0..10).map(|_| layout.step(&mut g)).collect::<Vec<_>>());
+            let locations = indices
+                .iter()
+                .map(|idx| g.node(*idx).unwrap().location())
+                .collect::<Vec<_>>();
+            (movements, locations)
+        };
+
+        let (movements, locations) = run(1);
+        assert!(movements.iter().all(|m| *m > 0.));
+        assert_eq!(run(4), (movements, locations));
+    }
+
+    #[test]
+    fn test_edge_weight() {
+        // springs balance repulsion at ideal_length / weight^(1/3)
+        assert!((settle(Euclidean::default(), 8.) - 25.).abs() < 1.);
+
+        let dist = settle(Euclidean::default(), -1.);
+        assert!(dist.is_finite());
+        assert!(dist > 50.);
+    }
+}
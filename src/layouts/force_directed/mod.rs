@@ -0,0 +1,5 @@
+mod force;
+mod layout;
+
+pub use force::{Euclidean, Force, Manhattan};
+pub use layout::{ForceDirected, State};
@@ -0,0 +1,364 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use egui::Pos2;
+use petgraph::{
+    stable_graph::{IndexType, NodeIndex},
+    visit::{EdgeRef, IntoEdgeReferences},
+    EdgeType,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    layouts::{Layout, LayoutState},
+    DisplayEdge, DisplayNode, Graph,
+};
+
+/// Number of alternating down and up sweeps of the crossing reduction.
+const SWEEPS: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    triggered: bool,
+    layer_spacing: f32,
+    node_spacing: f32,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            triggered: false,
+            layer_spacing: 50.,
+            node_spacing: 50.,
+        }
+    }
+}
+
+impl State {
+    /// Vertical distance in canvas units between consecutive layers.
+    ///
+    /// Default: `50.`
+    pub fn with_layer_spacing(mut self, spacing: f32) -> Self {
+        self.layer_spacing = spacing;
+        self
+    }
+
+    /// Horizontal distance in canvas units between neighbouring nodes of a layer.
+    ///
+    /// Default: `50.`
+    pub fn with_node_spacing(mut self, spacing: f32) -> Self {
+        self.node_spacing = spacing;
+        self
+    }
+}
+
+impl LayoutState for State {}
+
+/// Layered (Sugiyama-style) layout for directed acyclic graphs, e.g. pipelines and flowcharts. Applies once.
+///
+/// Nodes are assigned to layers by the longest path from the roots, so every edge points downwards,
+/// then ordered within layers to reduce edge crossings and centered horizontally. Edges spanning
+/// several layers reserve a slot in every layer they pass. Edges closing cycles are ignored,
+/// so cyclic graphs are laid out as well.
+#[derive(Debug, Default)]
+pub struct Layered {
+    state: State,
+}
+
+impl Layout<State> for Layered {
+    fn next<N, E, Ty, Ix, Dn, De>(&mut self, g: &mut Graph<N, E, Ty, Ix, Dn, De>)
+    where
+        N: Clone,
+        E: Clone,
+        Ty: EdgeType,
+        Ix: IndexType,
+        Dn: DisplayNode<N, E, Ty, Ix>,
+        De: DisplayEdge<N, E, Ty, Ix, Dn>,
+    {
+        if self.state.triggered {
+            return;
+        }
+
+        let nodes = g.g.node_indices().collect::<Vec<NodeIndex<Ix>>>();
+        let positions = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, idx)| (*idx, i))
+            .collect::<HashMap<_, _>>();
+        let edges =
+            g.g.edge_references()
+                .map(|e| (positions[&e.source()], positions[&e.target()]))
+                .collect::<Vec<_>>();
+
+        let dag = acyclic(nodes.len(), &edges);
+        let ranks = rank(nodes.len(), &dag);
+        for (row, layer) in order(&ranks, &dag).iter().enumerate() {
+            let offset = (layer.len() - 1) as f32 / 2.;
+            for (col, v) in layer.iter().enumerate() {
+                // slots of long edges have no nodes
+                let Some(idx) = nodes.get(*v) else {
+                    continue;
+                };
+
+                g.g[*idx].set_layout_location(Pos2::new(
+                    (col as f32 - offset) * self.state.node_spacing,
+                    row as f32 * self.state.layer_spacing,
+                ));
+            }
+        }
+
+        self.state.triggered = true;
+    }
+
+    fn state(&self) -> State {
+        self.state.clone()
+    }
+
+    fn from_state(state: State) -> impl Layout<State> {
+        Self { state }
+    }
+}
+
+/// Returns edges without self loops, duplicates and back edges found with depth-first search
+/// started from the roots. The remaining edges form a directed acyclic graph.
+fn acyclic(n: usize, edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut out = vec![vec![]; n];
+    let mut indegree = vec![0; n];
+    let mut seen = HashSet::new();
+    for (u, w) in edges {
+        if u != w && seen.insert((*u, *w)) {
+            out[*u].push(*w);
+            indegree[*w] += 1;
+        }
+    }
+
+    // 0 - not visited, 1 - on the current path, 2 - finished
+    let mut marks = vec![0_u8; n];
+    let mut res = vec![];
+    let roots = (0..n).filter(|v| indegree[*v] == 0);
+    for start in roots.chain(0..n) {
+        if marks[start] != 0 {
+            continue;
+        }
+
+        marks[start] = 1;
+        let mut stack = vec![(start, 0)];
+        while let Some((v, i)) = stack.last().copied() {
+            let Some(w) = out[v].get(i).copied() else {
+                marks[v] = 2;
+                stack.pop();
+                continue;
+            };
+
+            let last = stack.len() - 1;
+            stack[last].1 += 1;
+            match marks[w] {
+                0 => {
+                    marks[w] = 1;
+                    stack.push((w, 0));
+                    res.push((v, w));
+                }
+                // the edge closes a cycle
+                1 => {}
+                _ => res.push((v, w)),
+            }
+        }
+    }
+
+    res
+}
+
+/// Assigns layers to nodes of a directed acyclic graph by the longest path from the roots.
+fn rank(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut out = vec![vec![]; n];
+    let mut indegree = vec![0; n];
+    for (u, w) in edges {
+        out[*u].push(*w);
+        indegree[*w] += 1;
+    }
+
+    let mut ranks = vec![0; n];
+    let mut queue = (0..n)
+        .filter(|v| indegree[*v] == 0)
+        .collect::<VecDeque<_>>();
+    while let Some(v) = queue.pop_front() {
+        for w in &out[v] {
+            ranks[*w] = ranks[*w].max(ranks[v] + 1);
+            indegree[*w] -= 1;
+            if indegree[*w] == 0 {
+                queue.push_back(*w);
+            }
+        }
+    }
+
+    ranks
+}
+
+/// Splits edges spanning several layers with virtual vertices numbered after the nodes and orders
+/// vertices within layers by the barycenter heuristic. Returns the order with the fewest crossings found.
+fn order(ranks: &[usize], edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut ranks = ranks.to_vec();
+    let mut links = vec![];
+    for (u, w) in edges {
+        let mut prev = *u;
+        for r in ranks[*u] + 1..ranks[*w] {
+            ranks.push(r);
+            links.push((prev, ranks.len() - 1));
+            prev = ranks.len() - 1;
+        }
+        links.push((prev, *w));
+    }
+
+    let mut ups = vec![vec![]; ranks.len()];
+    let mut downs = vec![vec![]; ranks.len()];
+    for (u, w) in &links {
+        downs[*u].push(*w);
+        ups[*w].push(*u);
+    }
+
+    let mut layers = vec![vec![]; ranks.iter().max().map_or(0, |r| r + 1)];
+    for (v, r) in ranks.iter().enumerate() {
+        layers[*r].push(v);
+    }
+
+    let mut positions = vec![0; ranks.len()];
+    let mut best = layers.clone();
+    let mut best_crossings = crossings(&layers, &downs, &mut positions);
+    for sweep in 0..SWEEPS {
+        if sweep % 2 == 0 {
+            for r in 1..layers.len() {
+                reorder(&mut layers, r, r - 1, &ups, &mut positions);
+            }
+        } else {
+            for r in (0..layers.len().saturating_sub(1)).rev() {
+                reorder(&mut layers, r, r + 1, &downs, &mut positions);
+            }
+        }
+
+        let curr = crossings(&layers, &downs, &mut positions);
+        if curr < best_crossings {
+            best_crossings = curr;
+            best.clone_from(&layers);
+        }
+    }
+
+    best
+}
+
+/// Sorts the layer by the average position of the neighbours of its vertices in the fixed layer.
+/// Vertices without neighbours there keep their position.
+fn reorder(
+    layers: &mut [Vec<usize>],
+    layer: usize,
+    fixed: usize,
+    neighbours: &[Vec<usize>],
+    positions: &mut [usize],
+) {
+    for (i, v) in layers[fixed].iter().enumerate() {
+        positions[*v] = i;
+    }
+
+    let mut keyed = layers[layer]
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let adjacent = &neighbours[*v];
+            let key = if adjacent.is_empty() {
+                i as f32
+            } else {
+                adjacent.iter().map(|w| positions[*w] as f32).sum::<f32>() / adjacent.len() as f32
+            };
+            (key, *v)
+        })
+        .collect::<Vec<_>>();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    layers[layer] = keyed.into_iter().map(|(_, v)| v).collect();
+}
+
+/// Counts pairwise crossings of links between consecutive layers.
+fn crossings(layers: &[Vec<usize>], downs: &[Vec<usize>], positions: &mut [usize]) -> usize {
+    for layer in layers {
+        for (i, v) in layer.iter().enumerate() {
+            positions[*v] = i;
+        }
+    }
+
+    let mut res = 0;
+    for layer in layers {
+        let links = layer
+            .iter()
+            .flat_map(|u| downs[*u].iter().map(|w| (positions[*u], positions[*w])))
+            .collect::<Vec<_>>();
+        for (i, (u1, w1)) in links.iter().enumerate() {
+            res += links[i + 1..]
+                .iter()
+                .filter(|(u2, w2)| (u1 < u2 && w1 > w2) || (u1 > u2 && w1 < w2))
+                .count();
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::stable_graph::StableGraph;
+
+    use super::*;
+    use crate::Node;
+
+    fn layout(sg: &StableGraph<(), ()>) -> Vec<Pos2> {
+        let mut g = Graph::<()>::from(sg);
+        Layered::default().next(&mut g);
+        g.g.node_weights().map(Node::location).collect()
+    }
+
+    #[test]
+    fn test_longest_path_layers() {
+        let mut sg = StableGraph::new();
+        let root = sg.add_node(());
+        let mid = sg.add_node(());
+        let leaf = sg.add_node(());
+        let other_root = sg.add_node(());
+        sg.add_edge(root, mid, ());
+        sg.add_edge(mid, leaf, ());
+        sg.add_edge(root, leaf, ());
+        sg.add_edge(other_root, leaf, ());
+
+        let locs = layout(&sg);
+        assert_eq!(locs[0].y, 0.);
+        assert_eq!(locs[1].y, 50.);
+        assert_eq!(locs[2].y, 100.);
+        assert_eq!(locs[3].y, 0.);
+        assert_ne!(locs[0].x, locs[3].x);
+    }
+
+    #[test]
+    fn test_crossings_reduced() {
+        let mut sg = StableGraph::new();
+        let left = sg.add_node(());
+        let right = sg.add_node(());
+        let first_child = sg.add_node(());
+        let second_child = sg.add_node(());
+        sg.add_edge(left, second_child, ());
+        sg.add_edge(right, first_child, ());
+
+        let locs = layout(&sg);
+        assert_eq!(locs[0].x < locs[1].x, locs[3].x < locs[2].x);
+    }
+
+    #[test]
+    fn test_cycle_back_edge_skipped() {
+        let mut sg = StableGraph::new();
+        let first = sg.add_node(());
+        let second = sg.add_node(());
+        let third = sg.add_node(());
+        sg.add_edge(first, second, ());
+        sg.add_edge(second, third, ());
+        sg.add_edge(third, first, ());
+        sg.add_edge(third, third, ());
+
+        let ys = layout(&sg).iter().map(|p| p.y).collect::<Vec<_>>();
+        assert_eq!(ys, vec![0., 50., 100.]);
+    }
+}
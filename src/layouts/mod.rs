@@ -1,4 +1,6 @@
+pub mod force_directed;
 pub mod hierarchical;
+pub mod layered;
 pub mod random;
 
 mod layout;
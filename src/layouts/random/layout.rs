@@ -16,7 +16,8 @@ pub struct State {
 
 impl LayoutState for State {}
 
-/// Randomly places nodes on the canvas. Does not override existing locations. Applies once.
+/// Randomly places nodes on the canvas. Does not override existing locations, including the ones
+/// of dirty nodes added with [`crate::Graph`] helpers. Applies once.
 #[derive(Debug, Default)]
 pub struct Random {
     state: State,
@@ -37,7 +38,7 @@ impl Layout<State> for Random {
         }
 
         let mut rng = rand::thread_rng();
-        for node in g.g.node_weights_mut() {
+        for node in g.g.node_weights_mut().filter(|n| !n.dirty()) {
             node.set_layout_location(Pos2::new(
                 rng.gen_range(0. ..SPAWN_SIZE),
                 rng.gen_range(0. ..SPAWN_SIZE),
@@ -5,22 +5,38 @@ mod graph_view;
 mod helpers;
 mod layouts;
 mod metadata;
+mod parallel;
 mod settings;
+#[cfg(test)]
+mod test_util;
 
-pub use draw::{DefaultEdgeShape, DefaultNodeShape, DisplayEdge, DisplayNode, DrawContext};
-pub use elements::{Edge, EdgeProps, Node, NodeProps};
-pub use graph::Graph;
-pub use graph_view::{DefaultGraphView, GraphView};
+pub use draw::{
+    Colormap, DefaultEdgeShape, DefaultNodeShape, DisplayEdge, DisplayNode, DrawContext, Palette,
+};
+pub use elements::{Badge, BadgePosition, Edge, EdgeProps, Node, NodeProps};
+pub use graph::{Graph, LocationChange, SelectionChange};
+pub use graph_view::{DefaultGraphView, GraphResponse, GraphView};
 pub use helpers::{
     add_edge, add_edge_custom, add_node, add_node_custom, default_edge_transform,
     default_node_transform, node_size, random_graph, to_graph, to_graph_custom,
 };
+pub use layouts::force_directed::{
+    Euclidean as ForceEuclidean, Force, ForceDirected as LayoutForceDirected,
+    Manhattan as ForceManhattan, State as LayoutStateForceDirected,
+};
 pub use layouts::hierarchical::{
     Hierarchical as LayoutHierarchical, State as LayoutStateHierarchical,
 };
+pub use layouts::layered::{Layered as LayoutLayered, State as LayoutStateLayered};
 pub use layouts::random::{Random as LayoutRandom, State as LayoutStateRandom};
 pub use metadata::Metadata;
-pub use settings::{SettingsInteraction, SettingsNavigation, SettingsStyle};
+pub use parallel::MaybeSync;
+pub use settings::{
+    ArrowPlacement, EdgeCap, EdgeRouting, FitPadding, FitToScreen, FollowOnPan, OnResize,
+    ReciprocalEdges, SettingsInteraction, SettingsNavigation, SettingsStyle,
+};
+
+pub mod export;
+pub mod io;
 
-#[cfg(feature = "events")]
 pub mod events;
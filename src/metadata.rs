@@ -1,4 +1,4 @@
-use egui::{Id, Pos2, Rect, Vec2};
+use egui::{Id, Modifiers, Pos2, Rect, Vec2};
 use petgraph::{stable_graph::IndexType, EdgeType};
 use serde::{Deserialize, Serialize};
 
@@ -49,17 +49,73 @@ impl Bounds {
     }
 }
 
+/// Navigation state of the graph widget persisted between frames.
+///
+/// Two coordinate systems are used:
+/// - canvas coordinates are the units of node locations and of sizes in node and edge displays;
+/// - screen coordinates are egui points, the logical pixels egui uses for all layout and input.
+///
+/// Screen coordinates do not depend on `pixels_per_point`, egui converts points to physical pixels
+/// when shapes are tessellated. Zoom `1.` maps one canvas unit to one point, so node radii, stroke widths,
+/// hit tests and fit to screen behave the same on any DPI.
+///
+/// Conversions between the two systems are exposed as methods, e.g. [`Metadata::canvas_to_screen_pos`]
+/// positions custom egui widgets over nodes of the graph loaded with [`Metadata::load`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Metadata {
     /// Whether the frame is the first one
     pub first_frame: bool,
-    /// Current zoom factor
+    /// Current zoom factor, screen points per canvas unit
     pub zoom: f32,
-    /// Current pan offset
+    /// Current pan offset in screen points
     pub pan: Vec2,
-    /// Top left position of widget
+    /// Top left position of widget in screen points
     pub top_left: Pos2,
 
+    /// Start node index and cursor position in canvas coordinates of the edge being created
+    pub(crate) edge_creation: Option<(usize, Pos2)>,
+    /// Node index the edge being created ends at if dropped now
+    pub(crate) edge_creation_end: Option<usize>,
+    /// Node index under the cursor and cursor position in canvas coordinates of the last secondary click
+    pub(crate) context_menu: (Option<usize>, Pos2),
+    /// Position in canvas coordinates, time and modifiers of the single click waiting for a possible double click
+    pub(crate) pending_click: Option<(Pos2, f64, Modifiers)>,
+    /// Start time and current center in screen points of the ongoing multi-touch gesture
+    pub(crate) touch: Option<(f64, Pos2)>,
+    /// Index of the focused node and number of hops around it which are not dimmed
+    pub(crate) focus: Option<(usize, usize)>,
+    /// Index of the node kept in the center of the widget
+    pub(crate) follow: Option<usize>,
+    /// Index of the node focused with keyboard navigation
+    pub(crate) focused_node: Option<usize>,
+    /// Index of the node clicked last, cleared by a click on empty space
+    pub(crate) last_clicked_node: Option<usize>,
+    /// Index of the node the next range selection starts from
+    pub(crate) selection_anchor: Option<usize>,
+    /// Whether the graph should be fitted to the screen in the next frame
+    pub(crate) fit_requested: bool,
+    /// Size of the widget in the last frame
+    pub(crate) canvas_size: Vec2,
+    /// Indices of nodes to fit to the screen in the next frame
+    pub(crate) fit_nodes: Option<Vec<usize>>,
+    /// Index of the pressed node and pointer movement in screen points since the drag gesture started,
+    /// kept until the movement exceeds the drag threshold
+    pub(crate) drag_pending: Option<(usize, Vec2)>,
+    /// Location of the dragged node at the start of the drag gesture
+    pub(crate) drag_start: Option<Pos2>,
+    /// Index of the hovered edge
+    pub(crate) hovered_edge: Option<usize>,
+    /// Indices of the node and the edge under the pointer and the time the pointer moved over them
+    pub(crate) hover_start: Option<(Option<usize>, Option<usize>, f64)>,
+    /// Zoom and pan saved while the overview key is held
+    pub(crate) overview: Option<(f32, Vec2)>,
+    /// Whether layout and fit to screen are suspended for a batch of graph updates
+    pub(crate) batch: bool,
+    /// Hash of edge positions and bundling strength edge bundles were computed for, `0` if there are no bundles
+    pub(crate) bundling_hash: u64,
+    /// Generation of the graph nodes were colored by connected components for
+    pub(crate) components_generation: Option<u64>,
+
     /// State of bounds iteration
     bounds: Bounds,
 }
@@ -71,12 +127,38 @@ impl Default for Metadata {
             zoom: 1.,
             pan: Vec2::default(),
             top_left: Pos2::default(),
+            edge_creation: Option::default(),
+            edge_creation_end: Option::default(),
+            context_menu: (Option::default(), Pos2::default()),
+            pending_click: Option::default(),
+            touch: Option::default(),
+            focus: Option::default(),
+            follow: Option::default(),
+            focused_node: Option::default(),
+            last_clicked_node: Option::default(),
+            selection_anchor: Option::default(),
+            fit_requested: bool::default(),
+            fit_nodes: Option::default(),
+            canvas_size: Vec2::default(),
+            drag_pending: Option::default(),
+            drag_start: Option::default(),
+            hovered_edge: Option::default(),
+            hover_start: Option::default(),
+            overview: Option::default(),
+            batch: bool::default(),
+            bundling_hash: u64::default(),
+            components_generation: Option::default(),
             bounds: Bounds::default(),
         }
     }
 }
 
 impl Metadata {
+    /// Reads metadata of the graph widget from egui memory.
+    ///
+    /// Reading is non-destructive: it returns a copy and never changes stored values, so it can be used
+    /// outside of the widget, e.g. to show current zoom in a status bar rendered after the graph.
+    /// Returns default metadata if the widget was not shown yet.
     pub fn load(ui: &egui::Ui) -> Self {
         ui.data_mut(|data| {
             data.get_persisted::<Metadata>(Id::new(KEY))
@@ -84,24 +166,53 @@ impl Metadata {
         })
     }
 
+    /// Stores metadata in egui memory replacing values used by the graph widget in the next frame.
     pub fn save(self, ui: &mut egui::Ui) {
         ui.data_mut(|data| {
             data.insert_persisted(Id::new(KEY), self);
         });
     }
 
+    /// Current zoom factor, `1.` means canvas units are equal to screen points.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Current pan offset in screen coordinates.
+    pub fn pan(&self) -> Vec2 {
+        self.pan
+    }
+
+    /// Converts position in graph canvas coordinates, used by node locations, to screen coordinates.
     pub fn canvas_to_screen_pos(&self, pos: Pos2) -> Pos2 {
         (pos.to_vec2() * self.zoom + self.pan).to_pos2()
     }
 
+    /// Converts offset in canvas coordinates to screen coordinates. Offsets are only scaled by zoom.
+    pub fn canvas_to_screen_vec(&self, vec: Vec2) -> Vec2 {
+        vec * self.zoom
+    }
+
+    /// Converts size in canvas coordinates to screen coordinates.
     pub fn canvas_to_screen_size(&self, size: f32) -> f32 {
         size * self.zoom
     }
 
+    /// Converts position in screen coordinates, e.g. pointer position, to graph canvas coordinates.
     pub fn screen_to_canvas_pos(&self, pos: Pos2) -> Pos2 {
         ((pos.to_vec2() - self.pan) / self.zoom).to_pos2()
     }
 
+    /// Converts offset in screen coordinates, e.g. drag delta, to canvas coordinates.
+    pub fn screen_to_canvas_vec(&self, vec: Vec2) -> Vec2 {
+        vec / self.zoom
+    }
+
+    /// Converts size in screen coordinates, e.g. hit padding, to canvas coordinates.
+    pub fn screen_to_canvas_size(&self, size: f32) -> f32 {
+        size / self.zoom
+    }
+
     pub fn comp_iter_bounds<
         N: Clone,
         E: Clone,
@@ -125,3 +236,37 @@ impl Metadata {
         self.bounds = Bounds::default();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinates_round_trip() {
+        let meta = Metadata {
+            zoom: 2.,
+            pan: Vec2::new(10., -20.),
+            ..Default::default()
+        };
+
+        let pos = Pos2::new(3., 4.);
+        assert_eq!(meta.canvas_to_screen_pos(pos), Pos2::new(16., -12.));
+        assert_eq!(
+            meta.screen_to_canvas_pos(meta.canvas_to_screen_pos(pos)),
+            pos
+        );
+
+        let vec = Vec2::new(3., 4.);
+        assert_eq!(meta.canvas_to_screen_vec(vec), Vec2::new(6., 8.));
+        assert_eq!(
+            meta.screen_to_canvas_vec(meta.canvas_to_screen_vec(vec)),
+            vec
+        );
+
+        assert_eq!(meta.canvas_to_screen_size(3.), 6.);
+        assert_eq!(
+            meta.screen_to_canvas_size(meta.canvas_to_screen_size(3.)),
+            3.
+        );
+    }
+}
@@ -0,0 +1,15 @@
+/// Bound for types which are shared between threads when the `rayon` feature is enabled.
+/// Without the feature it is implemented for every type.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Sync> MaybeSync for T {}
+
+/// Bound for types which are shared between threads when the `rayon` feature is enabled.
+/// Without the feature it is implemented for every type.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSync for T {}
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
+use egui::{Color32, Key, Modifiers, Rect, Stroke, Vec2, Visuals};
+
 /// Represents graph interaction settings.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SettingsInteraction {
     pub(crate) dragging_enabled: bool,
     pub(crate) node_clicking_enabled: bool,
@@ -8,6 +12,50 @@ pub struct SettingsInteraction {
     pub(crate) edge_clicking_enabled: bool,
     pub(crate) edge_selection_enabled: bool,
     pub(crate) edge_selection_multi_enabled: bool,
+    pub(crate) edge_creation_enabled: bool,
+    pub(crate) edge_creation_modifier: Modifiers,
+    pub(crate) selection_multi_modifier: Option<Modifiers>,
+    pub(crate) selection_range_modifier: Option<Modifiers>,
+    pub(crate) axis_lock_modifier: Option<Modifiers>,
+    pub(crate) drag_bounds: Option<Rect>,
+    pub(crate) click_disambiguation: bool,
+    pub(crate) node_hit_padding: f32,
+    pub(crate) edge_hit_padding: f32,
+    pub(crate) keyboard_navigation_enabled: bool,
+    pub(crate) node_deletion_enabled: bool,
+    pub(crate) max_drag_step: Option<f32>,
+    pub(crate) drag_threshold: f32,
+    pub(crate) hover_delay: Duration,
+    pub(crate) edge_tooltips_enabled: bool,
+}
+
+impl Default for SettingsInteraction {
+    fn default() -> Self {
+        Self {
+            dragging_enabled: false,
+            node_clicking_enabled: false,
+            node_selection_enabled: false,
+            node_selection_multi_enabled: false,
+            edge_clicking_enabled: false,
+            edge_selection_enabled: false,
+            edge_selection_multi_enabled: false,
+            edge_creation_enabled: false,
+            edge_creation_modifier: Modifiers::SHIFT,
+            selection_multi_modifier: None,
+            selection_range_modifier: None,
+            axis_lock_modifier: None,
+            drag_bounds: None,
+            click_disambiguation: false,
+            node_hit_padding: 0.,
+            edge_hit_padding: 0.,
+            keyboard_navigation_enabled: false,
+            node_deletion_enabled: false,
+            max_drag_step: None,
+            drag_threshold: 0.,
+            hover_delay: Duration::ZERO,
+            edge_tooltips_enabled: false,
+        }
+    }
 }
 
 impl SettingsInteraction {
@@ -79,24 +127,221 @@ impl SettingsInteraction {
         self.edge_selection_multi_enabled = enabled;
         self
     }
+
+    /// Modifier which makes a click add to the selection instead of replacing it, e.g. [`Modifiers::COMMAND`].
+    ///
+    /// When set, plain click selects only the clicked node or edge and click with the modifier held
+    /// toggles it in the existing selection. Multiselection settings keep toggling on plain click as before.
+    ///
+    /// Default: `None`
+    pub fn with_multi_select_modifier(mut self, modifier: Option<Modifiers>) -> Self {
+        self.selection_multi_modifier = modifier;
+        self
+    }
+
+    /// Alias of [`SettingsInteraction::with_multi_select_modifier`].
+    ///
+    /// Default: `None`
+    pub fn with_selection_multi_modifier(self, modifier: Option<Modifiers>) -> Self {
+        self.with_multi_select_modifier(modifier)
+    }
+
+    /// Modifier which makes a click on a node select the range from the previously clicked node,
+    /// e.g. [`Modifiers::SHIFT`].
+    ///
+    /// The range is the shortest path between the nodes along edges in any direction. It is added
+    /// to the existing selection. If the nodes are not connected only the clicked node is selected.
+    ///
+    /// Default: `None`
+    pub fn with_selection_range_modifier(mut self, modifier: Option<Modifiers>) -> Self {
+        self.selection_range_modifier = modifier;
+        self
+    }
+
+    /// Modifier which constrains dragging of a node to the horizontal or vertical axis, e.g. [`Modifiers::SHIFT`].
+    ///
+    /// While the modifier is held the node moves only along the axis it moved farther along since
+    /// the drag started.
+    ///
+    /// Default: `None`
+    pub fn with_axis_lock_modifier(mut self, modifier: Option<Modifiers>) -> Self {
+        self.axis_lock_modifier = modifier;
+        self
+    }
+
+    /// Makes single and double clicks mutually exclusive.
+    ///
+    /// Single clicks are handled only after the double click delay of egui passes without a second click,
+    /// so double clicking a node does not also select or click it. Single clicks are delayed by that time.
+    ///
+    /// Default: `false`
+    pub fn with_click_disambiguation(mut self, enabled: bool) -> Self {
+        self.click_disambiguation = enabled;
+        self
+    }
+
+    /// Distance in screen points around node boundaries which still counts as hitting the node when clicking,
+    /// dragging or creating edges. Makes small nodes easier to hit without changing how they are drawn.
+    ///
+    /// Default: `0.`
+    pub fn with_node_hit_padding(mut self, padding: f32) -> Self {
+        self.node_hit_padding = padding;
+        self
+    }
+
+    /// Distance in screen points around edges which still counts as hitting the edge when clicking or hovering.
+    /// Makes thin edges easier to hit.
+    ///
+    /// Default: `0.`
+    pub fn with_edge_hit_padding(mut self, padding: f32) -> Self {
+        self.edge_hit_padding = padding;
+        self
+    }
+
+    /// Keyboard navigation between nodes. When the graph has keyboard focus, `Tab` and arrow keys move
+    /// the focus ring to the next or previous node and `Enter` or `Space` click the focused node.
+    /// The graph gets keyboard focus when it is clicked.
+    ///
+    /// Default: `false`
+    pub fn with_keyboard_navigation_enabled(mut self, enabled: bool) -> Self {
+        self.keyboard_navigation_enabled = enabled;
+        self
+    }
+
+    /// Removes selected nodes with their edges on `Delete` or `Backspace`. Keys are handled when the graph
+    /// has keyboard focus, which it gets when clicked, or when it is hovered and no other widget has focus.
+    /// Nodes marked as not interactable with `Node::set_interactable` are kept. The node selection is cleared
+    /// after deletion.
+    ///
+    /// Default: `false`
+    pub fn with_node_deletion_enabled(mut self, enabled: bool) -> Self {
+        self.node_deletion_enabled = enabled;
+        self
+    }
+
+    /// Alias of [`SettingsInteraction::with_node_deletion_enabled`]. The selection is cleared after deletion.
+    ///
+    /// Default: `false`
+    pub fn with_delete_key_enabled(self, enabled: bool) -> Self {
+        self.with_node_deletion_enabled(enabled)
+    }
+
+    /// Distance in screen points the pointer has to move from where a node was pressed before the node
+    /// starts following it, so small accidental movements during clicks do not nudge nodes.
+    /// Releasing the node before the threshold is reached counts as a click.
+    ///
+    /// Default: `0.`
+    pub fn with_drag_threshold(mut self, threshold: f32) -> Self {
+        self.drag_threshold = threshold;
+        self
+    }
+
+    /// Maximal distance in canvas units a dragged node moves in a single frame. Keeps the node from jumping
+    /// after frame drops or when dragging at tiny zoom, the node catches up with the pointer in the next frames.
+    ///
+    /// Default: `None`
+    pub fn with_max_drag_step(mut self, max_step: Option<f32>) -> Self {
+        self.max_drag_step = max_step;
+        self
+    }
+
+    /// Time the pointer has to rest over a node or an edge before it is marked as hovered,
+    /// which triggers hover highlight and [`crate::events::Event::EdgeHover`]. Moving the pointer
+    /// to another element restarts the timer, so sweeping across a dense graph does not flicker.
+    ///
+    /// Default: `Duration::ZERO`
+    pub fn with_hover_delay(mut self, delay: Duration) -> Self {
+        self.hover_delay = delay;
+        self
+    }
+
+    /// Shows the tooltip set with `GraphView::with_edge_tooltip` while the pointer is over an edge.
+    ///
+    /// Default: `false`
+    pub fn with_edge_tooltips_enabled(mut self, enabled: bool) -> Self {
+        self.edge_tooltips_enabled = enabled;
+        self
+    }
+
+    /// Limits the drag offset in canvas units to the maximal drag step.
+    pub(crate) fn clamp_drag_step(&self, delta: Vec2) -> Vec2 {
+        match self.max_drag_step {
+            Some(max_step) if delta.length() > max_step => delta.normalized() * max_step.max(0.),
+            _ => delta,
+        }
+    }
+
+    /// Region in canvas coordinates dragged nodes are kept in. Node locations are clamped into the rect
+    /// and reported node moves contain clamped positions. `None` allows dragging anywhere.
+    ///
+    /// Default: `None`
+    pub fn with_drag_bounds(mut self, bounds: Option<Rect>) -> Self {
+        self.drag_bounds = bounds;
+        self
+    }
+
+    #[cfg(feature = "events")]
+    /// Edge creation. Hold the edge creation modifier, `Shift` by default, and drag from one node to another
+    /// to create an edge between them.
+    ///
+    /// Available with the `events` feature only. The widget does not add the edge itself, it reports the intent
+    /// with `Event::EdgeCreate` so the client can create the edge with its own payload.
+    ///
+    /// While dragging, a preview line is drawn from the start node to the cursor. Dropping on empty space
+    /// or back on the start node cancels.
+    ///
+    /// Default: `false`
+    pub fn with_edge_creation_enabled(mut self, enabled: bool) -> Self {
+        self.edge_creation_enabled = enabled;
+        self
+    }
+
+    #[cfg(feature = "events")]
+    /// Modifier held when a drag starts on a node to create an edge instead of moving the node.
+    /// Pick one not used by [`SettingsInteraction::with_selection_range_modifier`] or
+    /// [`SettingsInteraction::with_axis_lock_modifier`]. [`Modifiers::NONE`] makes every drag from a node create an edge.
+    ///
+    /// Default: `Modifiers::SHIFT`
+    pub fn with_edge_creation_modifier(mut self, modifier: Modifiers) -> Self {
+        self.edge_creation_modifier = modifier;
+        self
+    }
 }
 
 /// Represents graph navigation settings.
 #[derive(Debug, Clone)]
 pub struct SettingsNavigation {
-    pub(crate) fit_to_screen_enabled: bool,
+    pub(crate) fit_to_screen: FitToScreen,
     pub(crate) zoom_and_pan_enabled: bool,
-    pub(crate) screen_padding: f32,
+    pub(crate) screen_padding: FitPadding,
     pub(crate) zoom_speed: f32,
+    pub(crate) zoom_sensitivity: f32,
+    pub(crate) zoom_inverted: bool,
+    pub(crate) touch_enabled: bool,
+    pub(crate) scroll_to_pan: bool,
+    pub(crate) scroll_pan_factor: Vec2,
+    pub(crate) overview_key: Option<Key>,
+    pub(crate) on_resize: OnResize,
+    pub(crate) follow_on_pan: FollowOnPan,
+    pub(crate) continuous_repaint: bool,
 }
 
 impl Default for SettingsNavigation {
     fn default() -> Self {
         Self {
-            screen_padding: 0.3,
+            screen_padding: FitPadding::Ratio(Vec2::splat(0.3)),
             zoom_speed: 0.1,
-            fit_to_screen_enabled: true,
+            zoom_sensitivity: 0.,
+            zoom_inverted: false,
+            touch_enabled: false,
+            scroll_to_pan: false,
+            scroll_pan_factor: Vec2::splat(1.),
+            overview_key: None,
+            on_resize: OnResize::Keep,
+            follow_on_pan: FollowOnPan::Pause,
+            fit_to_screen: FitToScreen::Continuous,
             zoom_and_pan_enabled: false,
+            continuous_repaint: true,
         }
     }
 }
@@ -110,12 +355,43 @@ impl SettingsNavigation {
     /// Fits the graph to the screen.
     ///
     /// With this enabled, the graph will be scaled and panned to fit the screen on every frame.
+    /// When disabled the graph is fitted once on the first frame, see [`FitToScreen::Once`].
     ///
-    /// You can configure the padding around the graph with `screen_padding` setting.
+    /// You can configure the padding around the graph with `screen_padding` or `fit_padding` setting.
     ///
     /// Default: `true`
     pub fn with_fit_to_screen_enabled(mut self, enabled: bool) -> Self {
-        self.fit_to_screen_enabled = enabled;
+        self.fit_to_screen = if enabled {
+            FitToScreen::Continuous
+        } else {
+            FitToScreen::Once
+        };
+        self
+    }
+
+    /// When the graph is fitted to the screen.
+    ///
+    /// Default: `FitToScreen::Continuous`
+    pub fn with_fit_to_screen(mut self, mode: FitToScreen) -> Self {
+        self.fit_to_screen = mode;
+        self
+    }
+
+    /// What happens with zoom and pan when the size of the widget changes by more than a few percent,
+    /// e.g. when a side panel collapses.
+    ///
+    /// Default: `OnResize::Keep`
+    pub fn with_on_resize(mut self, on_resize: OnResize) -> Self {
+        self.on_resize = on_resize;
+        self
+    }
+
+    /// What happens with the node followed by the camera, see [`crate::GraphView::follow_node`],
+    /// when the graph is panned manually.
+    ///
+    /// Default: `FollowOnPan::Pause`
+    pub fn with_follow_on_pan(mut self, follow_on_pan: FollowOnPan) -> Self {
+        self.follow_on_pan = follow_on_pan;
         self
     }
 
@@ -127,23 +403,299 @@ impl SettingsNavigation {
         self
     }
 
-    /// Padding around the graph when fitting to the screen.
+    /// Padding around the graph when fitting to the screen as a ratio of the graph size on both axes.
+    ///
+    /// Default: `0.3`
     pub fn with_screen_padding(mut self, padding: f32) -> Self {
+        self.screen_padding = FitPadding::Ratio(Vec2::splat(padding));
+        self
+    }
+
+    /// Padding around the graph when fitting to the screen, either relative to the graph size or in pixels,
+    /// separately for every axis.
+    ///
+    /// Default: `FitPadding::Ratio(Vec2::splat(0.3))`
+    pub fn with_fit_padding(mut self, padding: FitPadding) -> Self {
         self.screen_padding = padding;
         self
     }
 
     /// Controls the speed of the zoom.
+    ///
+    /// Default: `0.1`
     pub fn with_zoom_speed(mut self, speed: f32) -> Self {
         self.zoom_speed = speed;
         self
     }
+
+    /// Makes zoom proportional to the magnitude of the zoom input, so fast trackpad pinches zoom more
+    /// than small wheel ticks. The zoom input is scaled by `zoom_speed * sensitivity`, with the default
+    /// zoom speed sensitivity `10.` follows the input one to one.
+    ///
+    /// Zero makes every zoom input change zoom by the fixed `zoom_speed` step.
+    ///
+    /// Default: `0.`
+    pub fn with_zoom_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.zoom_sensitivity = sensitivity;
+        self
+    }
+
+    /// Inverts zoom direction.
+    ///
+    /// Default: `false`
+    pub fn with_zoom_inverted(mut self, inverted: bool) -> Self {
+        self.zoom_inverted = inverted;
+        self
+    }
+
+    /// Zoom with a pinch gesture around its center and pan with a two-finger drag on touch devices.
+    ///
+    /// Works independently of `zoom_and_pan_enabled`. Only gestures started inside the widget are handled.
+    ///
+    /// Default: `false`
+    pub fn with_touch_enabled(mut self, enabled: bool) -> Self {
+        self.touch_enabled = enabled;
+        self
+    }
+
+    /// Pans with mouse wheel or two-finger trackpad scroll. Zoom stays available with ctrl + scroll and pinch.
+    ///
+    /// Scrolling with shift held pans horizontally. Requires `zoom_and_pan_enabled`.
+    ///
+    /// Default: `false`
+    pub fn with_scroll_to_pan(mut self, enabled: bool) -> Self {
+        self.scroll_to_pan = enabled;
+        self
+    }
+
+    /// Multiplier of scroll delta on every axis applied when scrolling pans, negative values invert the axis.
+    ///
+    /// Default: `Vec2::splat(1.)`
+    pub fn with_scroll_pan_factor(mut self, factor: Vec2) -> Self {
+        self.scroll_pan_factor = factor;
+        self
+    }
+
+    /// Key which shows the whole graph while held. The graph is fitted to the screen when the key is pressed
+    /// over the widget and the previous zoom and pan are restored when it is released.
+    ///
+    /// Default: `None`
+    pub fn with_overview_key(mut self, key: Option<Key>) -> Self {
+        self.overview_key = key;
+        self
+    }
+
+    /// Converts zoom input factor to zoom step applied to the current zoom.
+    pub(crate) fn zoom_step(&self, delta: f32) -> f32 {
+        let sign = if self.zoom_inverted { -1. } else { 1. };
+        if self.zoom_sensitivity <= 0. {
+            return sign * self.zoom_speed * (delta - 1.).signum();
+        }
+
+        delta.powf(sign * self.zoom_speed * self.zoom_sensitivity) - 1.
+    }
+
+    /// Requests repaint on every frame.
+    ///
+    /// When disabled, repaint is requested only if the camera, the layout, the graph bounds or an ongoing
+    /// interaction or animation changed during the frame, so static graphs do not keep the app at max frame rate.
+    /// User input, e.g. hovering, still triggers repaints as usual in egui.
+    ///
+    /// Default: `true`
+    pub fn with_continuous_repaint(mut self, enabled: bool) -> Self {
+        self.continuous_repaint = enabled;
+        self
+    }
+
+    /// Alias of [`SettingsNavigation::with_continuous_repaint`]. Disable to repaint only while there is ongoing
+    /// work, i.e. a drag, a fade, a running layout or camera and graph bounds changes.
+    ///
+    /// Default: `true`
+    pub fn with_always_repaint(self, enabled: bool) -> Self {
+        self.with_continuous_repaint(enabled)
+    }
+}
+
+/// Mode of fitting the graph to the screen.
+///
+/// In every mode the graph is also fitted when requested with `GraphView::request_fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitToScreen {
+    /// Graph is fitted only on request.
+    Off,
+    /// Graph is fitted on the first frame, after that the user can zoom and pan freely.
+    Once,
+    /// Graph is fitted on every frame, which overrides user zoom and pan.
+    Continuous,
+}
+
+/// Reaction of the camera to size changes of the widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnResize {
+    /// Zoom and pan stay, the graph keeps its position relative to the top left corner.
+    Keep,
+    /// The canvas point in the center of the widget stays in the center.
+    Recenter,
+    /// The graph is fitted to the screen.
+    Fit,
+}
+
+/// Reaction of the camera following a node to manual panning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowOnPan {
+    /// Following is suspended while the graph is panned and the node is centered again afterwards.
+    Pause,
+    /// Following stops, the camera stays where it was panned to.
+    Stop,
+}
+
+/// Padding around the graph used by fit to screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FitPadding {
+    /// Padding as a fraction of the graph size for x and y axes, split evenly between both sides.
+    Ratio(Vec2),
+    /// Padding in screen points (logical pixels) kept free on each side of the graph for x and y axes.
+    Pixels(Vec2),
+}
+
+impl FitPadding {
+    /// Computes zoom which fits the graph of the given size into the canvas with the padding applied.
+    pub(crate) fn fit_zoom(&self, graph_size: Vec2, canvas_size: Vec2) -> f32 {
+        let (graph_size, canvas_size) = match *self {
+            FitPadding::Ratio(ratio) => (graph_size * (Vec2::splat(1.) + ratio), canvas_size),
+            FitPadding::Pixels(pixels) => {
+                (graph_size, (canvas_size - pixels * 2.).max(Vec2::splat(1.)))
+            }
+        };
+
+        // choose the minimum of the two zoom factors to avoid distortion
+        let zoom = canvas_size / graph_size;
+        zoom.x.min(zoom.y)
+    }
+}
+
+/// Position of direction arrows on edges of directed graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowPlacement {
+    /// Arrow tip touches the target node.
+    End,
+    /// Arrow is centered at the middle of the edge, which keeps arrows readable when many edges meet at a node.
+    Middle,
+    /// Arrows are drawn both at the middle and at the end.
+    Both,
+}
+
+impl ArrowPlacement {
+    pub(crate) fn at_end(self) -> bool {
+        matches!(self, ArrowPlacement::End | ArrowPlacement::Both)
+    }
+
+    pub(crate) fn at_middle(self) -> bool {
+        matches!(self, ArrowPlacement::Middle | ArrowPlacement::Both)
+    }
+}
+
+/// Path of edges connecting different nodes without parallel edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeRouting {
+    /// Straight line between nodes.
+    #[default]
+    Straight,
+    /// Smooth curve leaving and entering nodes horizontally or vertically, whichever offset between nodes is larger.
+    Bezier,
+    /// Right-angled path leaving and entering nodes horizontally or vertically, suited for flowcharts.
+    Orthogonal,
+}
+
+/// Drawing of a pair of directed edges going in opposite directions between the same nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReciprocalEdges {
+    /// Both edges are drawn along the same line, their arrows overlap.
+    #[default]
+    Overlap,
+    /// Edges bow to opposite sides.
+    Curved,
+    /// Only one of the edges is drawn, with arrows on both ends.
+    Combined,
+}
+
+/// Shape of the ends of edge lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeCap {
+    /// Lines end flat exactly at their end points.
+    Butt,
+    /// Lines end with half circles, which hides seams where thick edges meet arrows and nodes.
+    Round,
 }
 
 /// `SettingsStyle` stores settings for the style of the graph.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SettingsStyle {
     pub(crate) labels_always: bool,
+    pub(crate) fade_duration: f32,
+    pub(crate) color_by_component: bool,
+    pub(crate) bundling_strength: f32,
+    pub(crate) edge_hover_color: Option<Color32>,
+    pub(crate) edge_selected_color: Option<Color32>,
+    pub(crate) focus_dim: f32,
+    pub(crate) follow_theme: bool,
+    pub(crate) edges_on_top: bool,
+    pub(crate) scale_bar: bool,
+    pub(crate) arrow_placement: ArrowPlacement,
+    pub(crate) edge_cap: EdgeCap,
+    pub(crate) edge_routing: EdgeRouting,
+    pub(crate) reciprocal_edges: ReciprocalEdges,
+    pub(crate) reciprocal_edge_color: Option<Color32>,
+    pub(crate) edge_end_gap: f32,
+    pub(crate) fixed_node_screen_size: bool,
+    pub(crate) edge_gradient: bool,
+    pub(crate) node_stroke: Stroke,
+    pub(crate) node_stroke_selected: Stroke,
+    pub(crate) node_stroke_hovered: Stroke,
+    pub(crate) node_stroke_dragged: Stroke,
+    pub(crate) node_selected_color: Option<Color32>,
+    pub(crate) node_color_hovered: Option<Color32>,
+    pub(crate) node_color_dragged: Option<Color32>,
+    pub(crate) node_glow_selected: Option<Color32>,
+    pub(crate) node_glow_hovered: Option<Color32>,
+    pub(crate) background_color: Option<Color32>,
+    pub(crate) empty_text: Option<String>,
+}
+
+impl Default for SettingsStyle {
+    fn default() -> Self {
+        Self {
+            labels_always: false,
+            fade_duration: 0.3,
+            color_by_component: false,
+            bundling_strength: 0.,
+            edge_hover_color: None,
+            edge_selected_color: None,
+            focus_dim: 0.2,
+            follow_theme: true,
+            edges_on_top: false,
+            scale_bar: false,
+            arrow_placement: ArrowPlacement::End,
+            edge_cap: EdgeCap::Butt,
+            edge_routing: EdgeRouting::Straight,
+            reciprocal_edges: ReciprocalEdges::Overlap,
+            reciprocal_edge_color: None,
+            edge_end_gap: 0.,
+            fixed_node_screen_size: false,
+            edge_gradient: false,
+            node_stroke: Stroke::NONE,
+            node_stroke_selected: Stroke::NONE,
+            node_stroke_hovered: Stroke::NONE,
+            node_stroke_dragged: Stroke::NONE,
+            node_selected_color: None,
+            node_color_hovered: None,
+            node_color_dragged: None,
+            node_glow_selected: None,
+            node_glow_hovered: None,
+            background_color: None,
+            empty_text: None,
+        }
+    }
 }
 
 impl SettingsStyle {
@@ -163,4 +715,466 @@ impl SettingsStyle {
         self.labels_always = always;
         self
     }
+
+    /// Duration in seconds of node fade in and fade out animations.
+    ///
+    /// Zero makes nodes change opacity and get removed instantly.
+    ///
+    /// Default: `0.3`
+    pub fn with_fade_duration(mut self, seconds: f32) -> Self {
+        self.fade_duration = seconds;
+        self
+    }
+
+    /// Fills nodes of every connected component with a distinct color.
+    ///
+    /// Components are numbered in the order of their first node, so colors are stable while the graph
+    /// does not change. Colors set with `Node::set_color` take precedence.
+    ///
+    /// Default: `false`
+    pub fn with_color_by_component(mut self, enabled: bool) -> Self {
+        self.color_by_component = enabled;
+        self
+    }
+
+    /// Bundles nearly parallel straight edges lying close to each other into shared curved paths,
+    /// which reduces clutter of dense graphs.
+    ///
+    /// Strength in range `[0, 1]` controls how tight bundles are, `0` disables bundling. Bundles are
+    /// recomputed only when node positions or edges change.
+    ///
+    /// Default: `0.`
+    pub fn with_bundling_strength(mut self, strength: f32) -> Self {
+        self.bundling_strength = strength;
+        self
+    }
+
+    /// Color of the edge under the pointer. `None` uses the hovered widget color of egui visuals.
+    ///
+    /// Default: `None`
+    pub fn with_edge_hover_color(mut self, color: Option<Color32>) -> Self {
+        self.edge_hover_color = color;
+        self
+    }
+
+    /// Color of selected edges. `None` uses the active widget color of egui visuals.
+    ///
+    /// Default: `None`
+    pub fn with_edge_selected_color(mut self, color: Option<Color32>) -> Self {
+        self.edge_selected_color = color;
+        self
+    }
+
+    /// Sets fill of selected nodes and color of selected edges at once, e.g. to match the accent color of the app.
+    /// See [`SettingsStyle::with_node_selected_color`] and [`SettingsStyle::with_edge_selected_color`].
+    pub fn with_selected_colors(self, node: Option<Color32>, edge: Option<Color32>) -> Self {
+        self.with_node_selected_color(node)
+            .with_edge_selected_color(edge)
+    }
+
+    /// Opacity multiplier of nodes and edges outside of the focus set with `GraphView::focus_on`.
+    ///
+    /// Default: `0.2`
+    pub fn with_focus_dim(mut self, dim: f32) -> Self {
+        self.focus_dim = dim;
+        self
+    }
+
+    /// Derives default node and edge colors from visuals of the ui the graph is shown in,
+    /// so the graph follows light and dark themes. When disabled the colors of the dark theme are always used.
+    ///
+    /// Colors set explicitly, e.g. with `Node::set_color` or `with_edge_hover_color`, take precedence.
+    ///
+    /// Default: `true`
+    pub fn with_follow_theme(mut self, enabled: bool) -> Self {
+        self.follow_theme = enabled;
+        self
+    }
+
+    /// Draws edges over nodes instead of behind them, e.g. to keep edge labels visible.
+    ///
+    /// Default: `false`
+    pub fn with_edges_on_top(mut self, enabled: bool) -> Self {
+        self.edges_on_top = enabled;
+        self
+    }
+
+    /// Color the widget rect is filled with before the graph is drawn. Exported images and SVG documents
+    /// get the same background.
+    ///
+    /// `None` keeps the background transparent so the panel behind the widget shows through.
+    ///
+    /// Default: `None`
+    pub fn with_background_color(mut self, color: Option<Color32>) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Placeholder text, e.g. "No data", drawn in the center of the widget while the graph has no nodes.
+    ///
+    /// Default: `None`
+    pub fn with_empty_text(mut self, text: Option<String>) -> Self {
+        self.empty_text = text;
+        self
+    }
+
+    /// Shows a scale bar in the bottom left corner with its length in canvas units at the current zoom.
+    ///
+    /// Default: `false`
+    pub fn with_scale_bar(mut self, enabled: bool) -> Self {
+        self.scale_bar = enabled;
+        self
+    }
+
+    /// Alias of [`SettingsStyle::with_scale_bar`]. The length is rounded to 1, 2 or 5 times a power of ten.
+    ///
+    /// Default: `false`
+    pub fn with_show_scale_bar(self, enabled: bool) -> Self {
+        self.with_scale_bar(enabled)
+    }
+
+    /// Sets where direction arrows are drawn on edges of directed graphs.
+    ///
+    /// Default: `ArrowPlacement::End`
+    pub fn with_arrow_placement(mut self, placement: ArrowPlacement) -> Self {
+        self.arrow_placement = placement;
+        self
+    }
+
+    /// Sets the shape of edge line ends.
+    ///
+    /// Edges are antialiased by egui, feathering is configured for the whole app with
+    /// [`egui::epaint::TessellationOptions`].
+    ///
+    /// Default: `EdgeCap::Butt`
+    pub fn with_edge_cap(mut self, cap: EdgeCap) -> Self {
+        self.edge_cap = cap;
+        self
+    }
+
+    /// Sets path of edges between different nodes. Parallel edges, loops and bundled edges keep their curves.
+    ///
+    /// Default: `EdgeRouting::Straight`
+    pub fn with_edge_routing(mut self, routing: EdgeRouting) -> Self {
+        self.edge_routing = routing;
+        self
+    }
+
+    /// Draws edges with a gradient from the color of the source node to the color of the target node,
+    /// which shows direction when arrows are too small to see. Selected and hovered edges keep their color.
+    ///
+    /// Default: `false`
+    pub fn with_edge_gradient(mut self, enabled: bool) -> Self {
+        self.edge_gradient = enabled;
+        self
+    }
+
+    /// Keeps nodes of the default display the same size in screen points at any zoom, only their locations
+    /// are zoomed, like pins on a map. Node radius and strokes are then measured in screen points.
+    ///
+    /// Default: `false`
+    pub fn with_fixed_node_screen_size(mut self, fixed: bool) -> Self {
+        self.fixed_node_screen_size = fixed;
+        self
+    }
+
+    /// Gap in canvas units between ends of edges and node boundaries, so arrows do not touch nodes.
+    /// The gap scales with zoom like nodes do. Loops are not affected.
+    ///
+    /// Default: `0.`
+    pub fn with_edge_end_gap(mut self, gap: f32) -> Self {
+        self.edge_end_gap = gap;
+        self
+    }
+
+    /// Sets how a pair of opposite edges between the same nodes is drawn in directed graphs.
+    ///
+    /// With [`ReciprocalEdges::Combined`] the edge with the lower index is drawn for both.
+    ///
+    /// Default: `ReciprocalEdges::Overlap`
+    pub fn with_reciprocal_edges(mut self, reciprocal: ReciprocalEdges) -> Self {
+        self.reciprocal_edges = reciprocal;
+        self
+    }
+
+    /// Color of edges in directed graphs which have an edge going in the opposite direction, e.g. to tell
+    /// mutual links from one-way ones. Combine with [`ReciprocalEdges::Curved`] to keep both edges visible.
+    /// `None` draws them like other edges. Selected and hovered edges keep their state color.
+    ///
+    /// Default: `None`
+    pub fn with_reciprocal_edge_color(mut self, color: Option<Color32>) -> Self {
+        self.reciprocal_edge_color = color;
+        self
+    }
+
+    /// Outline of nodes in normal state. Width is in canvas units and scales with zoom.
+    ///
+    /// Node states are resolved by priority: dragged, selected, hovered, normal.
+    ///
+    /// Default: `Stroke::NONE`
+    pub fn with_node_stroke(mut self, stroke: Stroke) -> Self {
+        self.node_stroke = stroke;
+        self
+    }
+
+    /// Outline of selected nodes, also used for dragged nodes if their own stroke is not set.
+    ///
+    /// Default: `Stroke::NONE`
+    pub fn with_node_stroke_selected(mut self, stroke: Stroke) -> Self {
+        self.node_stroke_selected = stroke;
+        self
+    }
+
+    /// Outline of the node under the pointer, the normal stroke is used if not set.
+    ///
+    /// Default: `Stroke::NONE`
+    pub fn with_node_stroke_hovered(mut self, stroke: Stroke) -> Self {
+        self.node_stroke_hovered = stroke;
+        self
+    }
+
+    /// Outline of the dragged node, the selected stroke is used if not set.
+    ///
+    /// Default: `Stroke::NONE`
+    pub fn with_node_stroke_dragged(mut self, stroke: Stroke) -> Self {
+        self.node_stroke_dragged = stroke;
+        self
+    }
+
+    /// Fill of selected nodes overriding node colors, also used for dragged nodes if their own color is not set.
+    /// Without it selected nodes keep their color or use the active widget color of the theme.
+    ///
+    /// Default: `None`
+    pub fn with_node_selected_color(mut self, color: Option<Color32>) -> Self {
+        self.node_selected_color = color;
+        self
+    }
+
+    /// Fill of the node under the pointer overriding node colors. Without it the node keeps its color
+    /// or uses the hovered widget color of the theme.
+    ///
+    /// Default: `None`
+    pub fn with_node_color_hovered(mut self, color: Option<Color32>) -> Self {
+        self.node_color_hovered = color;
+        self
+    }
+
+    /// Fill of the dragged node overriding node colors, the selected color is used if not set.
+    ///
+    /// Default: `None`
+    pub fn with_node_color_dragged(mut self, color: Option<Color32>) -> Self {
+        self.node_color_dragged = color;
+        self
+    }
+
+    /// Soft halo drawn around selected and dragged nodes.
+    ///
+    /// Default: `None`
+    pub fn with_node_glow_selected(mut self, color: Option<Color32>) -> Self {
+        self.node_glow_selected = color;
+        self
+    }
+
+    /// Soft halo drawn around the node under the pointer.
+    ///
+    /// Default: `None`
+    pub fn with_node_glow_hovered(mut self, color: Option<Color32>) -> Self {
+        self.node_glow_hovered = color;
+        self
+    }
+
+    /// Resolves halo color of a node in the given states with the same priority as [`SettingsStyle::node_appearance`].
+    pub(crate) fn node_glow(
+        &self,
+        selected: bool,
+        dragged: bool,
+        hovered: bool,
+    ) -> Option<Color32> {
+        if selected || dragged {
+            return self.node_glow_selected;
+        }
+        if hovered {
+            return self.node_glow_hovered;
+        }
+
+        None
+    }
+
+    /// Multiplier of node sizes in canvas units at the given zoom, `None` if nodes scale with zoom.
+    pub(crate) fn node_size_scale(&self, zoom: f32) -> Option<f32> {
+        self.fixed_node_screen_size.then(|| 1. / zoom)
+    }
+
+    /// Resolves fill override and outline of a node in the given states by priority:
+    /// dragged, selected, hovered, normal.
+    pub(crate) fn node_appearance(
+        &self,
+        selected: bool,
+        dragged: bool,
+        hovered: bool,
+    ) -> (Option<Color32>, Stroke) {
+        let or_stroke = |stroke: Stroke, fallback: Stroke| {
+            if stroke.is_empty() {
+                fallback
+            } else {
+                stroke
+            }
+        };
+
+        if dragged {
+            return (
+                self.node_color_dragged.or(self.node_selected_color),
+                or_stroke(self.node_stroke_dragged, self.node_stroke_selected),
+            );
+        }
+        if selected {
+            return (self.node_selected_color, self.node_stroke_selected);
+        }
+        if hovered {
+            return (
+                self.node_color_hovered,
+                or_stroke(self.node_stroke_hovered, self.node_stroke),
+            );
+        }
+
+        (None, self.node_stroke)
+    }
+
+    /// Resolves visuals used by default displays from visuals of the ui.
+    pub(crate) fn visuals(&self, ui_visuals: &Visuals) -> Visuals {
+        if self.follow_theme {
+            ui_visuals.clone()
+        } else {
+            Visuals::dark()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_drag_step() {
+        let delta = Vec2::new(30., 40.);
+        assert_eq!(SettingsInteraction::new().clamp_drag_step(delta), delta);
+
+        let settings = SettingsInteraction::new().with_max_drag_step(Some(10.));
+        assert_eq!(settings.clamp_drag_step(delta), Vec2::new(6., 8.));
+        assert_eq!(
+            settings.clamp_drag_step(Vec2::new(3., 4.)),
+            Vec2::new(3., 4.)
+        );
+    }
+
+    #[test]
+    fn test_node_glow() {
+        let style = SettingsStyle::default()
+            .with_node_glow_selected(Some(Color32::RED))
+            .with_node_glow_hovered(Some(Color32::GREEN));
+
+        assert_eq!(style.node_glow(false, false, false), None);
+        assert_eq!(style.node_glow(false, false, true), Some(Color32::GREEN));
+        assert_eq!(style.node_glow(true, false, true), Some(Color32::RED));
+        assert_eq!(style.node_glow(false, true, false), Some(Color32::RED));
+    }
+
+    #[test]
+    fn test_node_appearance_priority() {
+        let stroke = |width| Stroke::new(width, Color32::WHITE);
+        let style = SettingsStyle::default()
+            .with_node_stroke(stroke(1.))
+            .with_node_stroke_selected(stroke(2.))
+            .with_node_stroke_hovered(stroke(3.))
+            .with_node_stroke_dragged(stroke(4.))
+            .with_node_selected_color(Some(Color32::RED))
+            .with_node_color_hovered(Some(Color32::GREEN))
+            .with_node_color_dragged(Some(Color32::BLUE));
+
+        assert_eq!(
+            style.node_appearance(false, false, false),
+            (None, stroke(1.))
+        );
+        assert_eq!(
+            style.node_appearance(false, false, true),
+            (Some(Color32::GREEN), stroke(3.))
+        );
+        assert_eq!(
+            style.node_appearance(true, false, true),
+            (Some(Color32::RED), stroke(2.))
+        );
+        assert_eq!(
+            style.node_appearance(true, true, true),
+            (Some(Color32::BLUE), stroke(4.))
+        );
+
+        // unset dragged and hovered styles fall back to selected and normal ones
+        let style = SettingsStyle::default()
+            .with_node_stroke(stroke(1.))
+            .with_node_stroke_selected(stroke(2.))
+            .with_node_selected_color(Some(Color32::RED));
+        assert_eq!(
+            style.node_appearance(false, true, true),
+            (Some(Color32::RED), stroke(2.))
+        );
+        assert_eq!(
+            style.node_appearance(false, false, true),
+            (None, stroke(1.))
+        );
+    }
+
+    #[test]
+    fn test_zoom_step_fixed() {
+        let settings = SettingsNavigation::new();
+
+        assert_eq!(settings.zoom_step(1.01), 0.1);
+        assert_eq!(settings.zoom_step(2.), 0.1);
+        assert_eq!(settings.zoom_step(0.5), -0.1);
+        assert_eq!(settings.with_zoom_inverted(true).zoom_step(2.), -0.1);
+    }
+
+    #[test]
+    fn test_zoom_step_proportional() {
+        let settings = SettingsNavigation::new().with_zoom_sensitivity(10.);
+
+        assert!((settings.zoom_step(1.5) - 0.5).abs() < 1e-5);
+        assert!((settings.zoom_step(0.5) + 0.5).abs() < 1e-5);
+        assert!(settings.zoom_step(1.01) < settings.zoom_step(1.1));
+
+        // inverted zoom step reverts the original one
+        let inverted = settings.with_zoom_inverted(true);
+        assert!(((1. + inverted.zoom_step(2.)) * 2. - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_fit_zoom_ratio() {
+        let padding = FitPadding::Ratio(Vec2::new(1., 0.));
+
+        assert_eq!(
+            padding.fit_zoom(Vec2::new(100., 100.), Vec2::new(400., 100.)),
+            1.
+        );
+        assert_eq!(
+            padding.fit_zoom(Vec2::new(100., 10.), Vec2::new(100., 100.)),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_fit_zoom_pixels() {
+        let padding = FitPadding::Pixels(Vec2::new(50., 0.));
+
+        // padding does not depend on the graph aspect ratio
+        assert_eq!(
+            padding.fit_zoom(Vec2::new(100., 10.), Vec2::new(300., 1000.)),
+            2.
+        );
+        assert_eq!(
+            padding.fit_zoom(Vec2::new(1000., 10.), Vec2::new(300., 1000.)),
+            0.2
+        );
+
+        // canvas smaller than padding does not flip zoom
+        assert!(padding.fit_zoom(Vec2::new(10., 10.), Vec2::new(50., 50.)) > 0.);
+    }
 }
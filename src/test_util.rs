@@ -0,0 +1,16 @@
+use egui::Pos2;
+use petgraph::stable_graph::StableGraph;
+
+use crate::Graph;
+
+/// Two nodes apart from each other connected with a single edge.
+pub(crate) fn graph() -> Graph {
+    let mut g = StableGraph::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    g.add_edge(a, b, ());
+
+    let mut g = Graph::from(&g);
+    g.node_mut(b).unwrap().set_location(Pos2::new(50., 20.));
+    g
+}